@@ -0,0 +1,528 @@
+use crate::{
+    pathfinding_algorithms::{find_pathfinder, pathfinder_names, MapConfig, MapError, Placement},
+    sorting_algorithms::{
+        distribution_names, find_distribution, find_searcher, find_selector, find_sorter,
+        searcher_names, selector_names, sorter_names, Distribution, GapSequence, HeapKind, HeapOp,
+        PartitionScheme, PivotRule, SortConfig, SorterOptions,
+    },
+};
+use std::{error::Error, fmt, str::FromStr};
+
+pub const USAGE: &str = "\
+Usage: algorithms [COMMAND] [OPTIONS]
+
+Commands:
+  sort <ALGORITHM>   Visualize one sorting algorithm
+  select <ALGORITHM> Visualize one selection algorithm finding the value of --rank
+  search <ALGORITHM> Visualize one search algorithm looking for --target in the sorted values
+  path <ALGORITHM>   Visualize one pathfinding algorithm
+  heap               Run priority queue operations on a binary heap, read from --ops or
+                     typed one per line
+  list               List the available algorithms
+  demo               Run every algorithm one after another (default)
+  help               Print this message
+
+Options:
+  --size <N>         Number of values to sort, or the width and height of the map
+  --min <N>          Smallest value to sort [default: 0]
+  --max <N>          Largest value to sort [default: 50]
+  --values <LIST>    Sort these comma separated values instead of random ones
+  --input <KIND>     Shape of the values to sort: random, sorted, reversed, nearly-sorted,
+                     few-unique, organ-pipe, sawtooth, all-equal or gaussian [default: random]
+  --gaps <SEQUENCE>  Gap sequence for shellsort: shell, knuth, sedgewick, ciura or tokuda
+                     [default: ciura]
+  --base <N>         Digit base for the radix sorts, and for counting sort when the values
+                     are too far apart to count, from 2 to 1024 [default: 10]
+  --rank <K>         Rank of the value to select, 1 for the smallest [default: the median]
+  --target <N>       Value to search for [default: a random one of the values]
+  --arity <N>        Children per node for daryheapsort, from 2 to 16 [default: 3]
+  --shuffles <N>     Shuffles bogosort tries before giving up. It only has a real chance on
+                     about 6 values or fewer, so pair it with a small --size
+                     [default: 4 times the orderings of up to 6 values, at least 100]
+  --pivot <RULE>     Pivot rule for quicksort: first, last, middle, random, median-of-three
+                     or ninther [default: last]
+  --partition <KIND> Partition scheme for quicksort: lomuto, hoare, three-way or dual-pivot
+                     [default: lomuto]
+  --ops <SCRIPT>     Heap operations separated by ';': build <VALUES>, insert <N>, extract,
+                     peek, increase <INDEX> <KEY> and delete <INDEX>
+  --min-heap         Keep the smallest value on top of the heap instead of the largest
+  --width <N>        Width of the map [default: 30]
+  --height <N>       Height of the map [default: 30]
+  --start <X,Y>      Start square of the map, or random [default: 0,0]
+  --end <X,Y>        End square of the map, or random [default: random]
+  --min-distance <N> Smallest distance from the start to a random end
+  --seed <SEED>      Seed for the random input, to replay an earlier run
+  --speed <X>        Animation speed multiplier, 2 is twice as fast [default: 1]
+  --audio            Play the algorithm as it runs
+  --headless         Run without terminal output, audio or delays";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Sort(String),
+    Select(String),
+    Search(String),
+    Path(String),
+    Heap,
+    List,
+    Demo,
+    Help,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    pub size: Option<usize>,
+    pub min: Option<i32>,
+    pub max: Option<i32>,
+    pub values: Option<Vec<i32>>,
+    pub distribution: Distribution,
+    pub gaps: GapSequence,
+    pub base: u32,
+    pub arity: usize,
+    pub shuffles: Option<usize>, // Bogosort's cap, picked from the length when None
+    pub rank: Option<usize>,     // Rank to select, counted from 1
+    pub target: Option<i32>,     // Value to search for, a random one of the values when None
+    pub pivot: PivotRule,
+    pub scheme: PartitionScheme,
+    pub ops: Option<Vec<HeapOp>>, // Scripted heap operations, read from stdin when None
+    pub heap_kind: HeapKind,
+    pub width: Option<usize>,
+    pub height: Option<usize>,
+    pub start: Option<Placement>,
+    pub end: Option<Placement>,
+    pub min_distance: Option<usize>,
+    pub seed: Option<u64>,
+    pub speed: f32,
+    pub audio: bool,
+    pub headless: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            size: None,
+            min: None,
+            max: None,
+            values: None,
+            distribution: Distribution::Random,
+            gaps: GapSequence::Ciura,
+            base: 10,
+            arity: 3,
+            shuffles: None,
+            rank: None,
+            target: None,
+            pivot: PivotRule::Last,
+            scheme: PartitionScheme::Lomuto,
+            ops: None,
+            heap_kind: HeapKind::Max,
+            width: None,
+            height: None,
+            start: None,
+            end: None,
+            min_distance: None,
+            seed: None,
+            speed: 1.0,
+            audio: false,
+            headless: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cli {
+    pub command: Command,
+    pub options: Options,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliError {
+    UnknownCommand(String),
+    UnknownOption(String),
+    MissingAlgorithm(&'static str),
+    UnknownAlgorithm {
+        kind: &'static str,
+        name: String,
+        available: Vec<&'static str>,
+    },
+    UnknownChoice {
+        option: &'static str,
+        name: String,
+        available: Vec<&'static str>,
+    },
+    MissingValue(String),
+    InvalidValue {
+        option: String,
+        value: String,
+    },
+    UnexpectedArgument(String),
+    ConflictingOptions(&'static str, &'static str),
+    InvalidMap(MapError),
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownCommand(command) => write!(f, "unknown command '{}'", command),
+            Self::UnknownOption(option) => write!(f, "unknown option '{}'", option),
+            Self::MissingAlgorithm(kind) => {
+                write!(f, "missing {} algorithm name, run 'list' to see them", kind)
+            }
+            Self::UnknownAlgorithm {
+                kind,
+                name,
+                available,
+            } => write!(
+                f,
+                "unknown {} algorithm '{}', expected one of: {}",
+                kind,
+                name,
+                available.join(", ")
+            ),
+            Self::UnknownChoice {
+                option,
+                name,
+                available,
+            } => write!(
+                f,
+                "unknown {} '{}', expected one of: {}",
+                option,
+                name,
+                available.join(", ")
+            ),
+            Self::MissingValue(option) => write!(f, "option '{}' needs a value", option),
+            Self::InvalidValue { option, value } => {
+                write!(f, "invalid value '{}' for option '{}'", value, option)
+            }
+            Self::UnexpectedArgument(arg) => write!(f, "unexpected argument '{}'", arg),
+            Self::ConflictingOptions(option, other) => {
+                write!(f, "option '{}' cannot be used with '{}'", option, other)
+            }
+            Self::InvalidMap(err) => write!(f, "invalid map: {}", err),
+        }
+    }
+}
+
+impl Error for CliError {}
+
+impl Cli {
+    /// Parses the command line, without the program name.
+    pub fn parse<I>(args: I) -> Result<Self, CliError>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut options = Options::default();
+        let mut positional = Vec::new();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            if !arg.starts_with('-') {
+                positional.push(arg);
+                continue;
+            }
+            // Accept both `--size 10` and `--size=10`
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+                None => (arg.clone(), None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| CliError::MissingValue(flag.clone()))
+            };
+            match flag.as_str() {
+                "--size" => {
+                    let size: usize = parse_value(&flag, &value()?)?;
+                    if size < 2 {
+                        return Err(invalid(&flag, &size.to_string()));
+                    }
+                    options.size = Some(size);
+                }
+                "--min" => options.min = Some(parse_value(&flag, &value()?)?),
+                "--max" => options.max = Some(parse_value(&flag, &value()?)?),
+                "--values" => {
+                    let raw = value()?;
+                    let values = raw
+                        .split(',')
+                        .filter(|part| !part.trim().is_empty())
+                        .map(|part| parse_value(&flag, part))
+                        .collect::<Result<Vec<i32>, _>>()?;
+                    if values.is_empty() {
+                        return Err(invalid(&flag, &raw));
+                    }
+                    options.values = Some(values);
+                }
+                "--input" => {
+                    let name = value()?;
+                    options.distribution =
+                        find_distribution(&name).ok_or_else(|| CliError::UnknownChoice {
+                            option: "input",
+                            name,
+                            available: distribution_names(),
+                        })?;
+                }
+                "--gaps" => {
+                    let name = value()?;
+                    options.gaps =
+                        GapSequence::find(&name).ok_or_else(|| CliError::UnknownChoice {
+                            option: "gap sequence",
+                            name,
+                            available: GapSequence::ALL.iter().map(|gaps| gaps.name()).collect(),
+                        })?;
+                }
+                "--base" => {
+                    let raw = value()?;
+                    let base: u32 = parse_value(&flag, &raw)?;
+                    if !(2..=1024).contains(&base) {
+                        return Err(invalid(&flag, &raw));
+                    }
+                    options.base = base;
+                }
+                "--rank" => {
+                    let raw = value()?;
+                    let rank: usize = parse_value(&flag, &raw)?;
+                    if rank == 0 {
+                        return Err(invalid(&flag, &raw));
+                    }
+                    options.rank = Some(rank);
+                }
+                "--target" => options.target = Some(parse_value(&flag, &value()?)?),
+                "--arity" => {
+                    let raw = value()?;
+                    let arity: usize = parse_value(&flag, &raw)?;
+                    if !(2..=16).contains(&arity) {
+                        return Err(invalid(&flag, &raw));
+                    }
+                    options.arity = arity;
+                }
+                "--shuffles" => {
+                    let raw = value()?;
+                    let shuffles: usize = parse_value(&flag, &raw)?;
+                    if shuffles == 0 {
+                        return Err(invalid(&flag, &raw));
+                    }
+                    options.shuffles = Some(shuffles);
+                }
+                "--pivot" => {
+                    let name = value()?;
+                    options.pivot =
+                        PivotRule::find(&name).ok_or_else(|| CliError::UnknownChoice {
+                            option: "pivot rule",
+                            name,
+                            available: PivotRule::ALL.iter().map(|rule| rule.name()).collect(),
+                        })?;
+                }
+                "--partition" => {
+                    let name = value()?;
+                    options.scheme =
+                        PartitionScheme::find(&name).ok_or_else(|| CliError::UnknownChoice {
+                            option: "partition scheme",
+                            name,
+                            available: PartitionScheme::ALL
+                                .iter()
+                                .map(|scheme| scheme.name())
+                                .collect(),
+                        })?;
+                }
+                "--ops" => {
+                    let script = value()?;
+                    let ops = HeapOp::parse_script(&script).map_err(|op| invalid(&flag, &op))?;
+                    options.ops = Some(ops);
+                }
+                "--min-heap" => options.heap_kind = HeapKind::Min,
+                "--width" => options.width = Some(parse_value(&flag, &value()?)?),
+                "--height" => options.height = Some(parse_value(&flag, &value()?)?),
+                "--start" => options.start = Some(parse_placement(&flag, &value()?)?),
+                "--end" => options.end = Some(parse_placement(&flag, &value()?)?),
+                "--min-distance" => options.min_distance = Some(parse_value(&flag, &value()?)?),
+                "--seed" => options.seed = Some(parse_value(&flag, &value()?)?),
+                "--speed" => {
+                    let raw = value()?;
+                    let speed: f32 = parse_value(&flag, &raw)?;
+                    if !speed.is_finite() || speed <= 0.0 {
+                        return Err(invalid(&flag, &raw));
+                    }
+                    options.speed = speed;
+                }
+                "--audio" => options.audio = true,
+                "--headless" => options.headless = true,
+                "-h" | "--help" => positional.insert(0, "help".to_string()),
+                _ => return Err(CliError::UnknownOption(arg)),
+            }
+        }
+
+        let defaults = SortConfig::default();
+        let (min, max) = (
+            options.min.unwrap_or(defaults.min),
+            options.max.unwrap_or(defaults.max),
+        );
+        if min > max {
+            return Err(invalid("--max", &max.to_string()));
+        }
+        // A fixed end is never drawn, so a distance to draw it at would go unused
+        if options.min_distance.is_some() && matches!(options.end, Some(Placement::Fixed(..))) {
+            return Err(CliError::ConflictingOptions(
+                "--min-distance",
+                "--end <X,Y>",
+            ));
+        }
+        options
+            .map_config()
+            .validate()
+            .map_err(CliError::InvalidMap)?;
+        if let Some(rank) = options.rank {
+            if rank > options.sort_config().len {
+                return Err(invalid("--rank", &rank.to_string()));
+            }
+        }
+
+        let mut positional = positional.into_iter();
+        let command = match positional.next().as_deref() {
+            None | Some("demo") => Command::Demo,
+            Some("list") => Command::List,
+            Some("heap") => Command::Heap,
+            Some("help") => Command::Help,
+            Some("sort") => {
+                let name = positional
+                    .next()
+                    .ok_or(CliError::MissingAlgorithm("sorting"))?;
+                if find_sorter(&name).is_none() {
+                    return Err(CliError::UnknownAlgorithm {
+                        kind: "sorting",
+                        name,
+                        available: sorter_names(),
+                    });
+                }
+                Command::Sort(name)
+            }
+            Some("select") => {
+                let name = positional
+                    .next()
+                    .ok_or(CliError::MissingAlgorithm("selection"))?;
+                if find_selector(&name).is_none() {
+                    return Err(CliError::UnknownAlgorithm {
+                        kind: "selection",
+                        name,
+                        available: selector_names(),
+                    });
+                }
+                Command::Select(name)
+            }
+            Some("search") => {
+                let name = positional
+                    .next()
+                    .ok_or(CliError::MissingAlgorithm("search"))?;
+                if find_searcher(&name).is_none() {
+                    return Err(CliError::UnknownAlgorithm {
+                        kind: "search",
+                        name,
+                        available: searcher_names(),
+                    });
+                }
+                Command::Search(name)
+            }
+            Some("path") => {
+                let name = positional
+                    .next()
+                    .ok_or(CliError::MissingAlgorithm("pathfinding"))?;
+                if find_pathfinder(&name).is_none() {
+                    return Err(CliError::UnknownAlgorithm {
+                        kind: "pathfinding",
+                        name,
+                        available: pathfinder_names(),
+                    });
+                }
+                Command::Path(name)
+            }
+            Some(other) => return Err(CliError::UnknownCommand(other.to_string())),
+        };
+        if let Some(extra) = positional.next() {
+            if command != Command::Help {
+                return Err(CliError::UnexpectedArgument(extra));
+            }
+        }
+
+        Ok(Self { command, options })
+    }
+}
+
+fn parse_value<T: FromStr>(option: &str, value: &str) -> Result<T, CliError> {
+    value.trim().parse().map_err(|_| invalid(option, value))
+}
+
+// Parses an `X,Y` map square
+fn parse_square(option: &str, value: &str) -> Result<(usize, usize), CliError> {
+    let (x, y) = value
+        .split_once(',')
+        .ok_or_else(|| invalid(option, value))?;
+    Ok((parse_value(option, x)?, parse_value(option, y)?))
+}
+
+// Parses a map square given as `X,Y`, or `random` to have the map draw one
+fn parse_placement(option: &str, value: &str) -> Result<Placement, CliError> {
+    if value.trim().eq_ignore_ascii_case("random") {
+        return Ok(Placement::Random);
+    }
+    let (x, y) = parse_square(option, value)?;
+    Ok(Placement::Fixed(x, y))
+}
+
+fn invalid(option: &str, value: &str) -> CliError {
+    CliError::InvalidValue {
+        option: option.to_string(),
+        value: value.to_string(),
+    }
+}
+
+impl Options {
+    /// Builds the input description for sorting runs from the size, range and values options.
+    pub fn sort_config(&self) -> SortConfig {
+        if let Some(values) = &self.values {
+            return SortConfig::from_values(values.clone());
+        }
+        let defaults = SortConfig::default();
+        SortConfig {
+            len: self.size.unwrap_or(defaults.len),
+            min: self.min.unwrap_or(defaults.min),
+            max: self.max.unwrap_or(defaults.max),
+            distribution: self.distribution,
+            values: None,
+        }
+    }
+
+    /// Index of the value to select, counted from 0. Defaults to the lower median.
+    pub fn select_index(&self) -> usize {
+        let len = self.sort_config().len;
+        self.rank.unwrap_or(len.div_ceil(2)).clamp(1, len.max(1)) - 1
+    }
+
+    /// Collects the variant choices for sorting algorithms that have them.
+    pub fn sorter_options(&self) -> SorterOptions {
+        SorterOptions {
+            gaps: self.gaps,
+            base: self.base,
+            pivot: self.pivot,
+            scheme: self.scheme,
+            arity: self.arity,
+            shuffles: self.shuffles,
+        }
+    }
+
+    /// Builds the map layout for pathfinding runs. `--size` sets both dimensions unless
+    /// `--width` or `--height` override them.
+    pub fn map_config(&self) -> MapConfig {
+        let defaults = MapConfig::default();
+        let width = self.width.or(self.size).unwrap_or(defaults.width);
+        let height = self.height.or(self.size).unwrap_or(defaults.height);
+        let mut config = MapConfig::new(width, height);
+        if let Some(start) = self.start {
+            config.start = start;
+        }
+        if let Some(end) = self.end {
+            config.end = end;
+        }
+        if let Some(min_distance) = self.min_distance {
+            config.min_distance = min_distance;
+        }
+        config
+    }
+}
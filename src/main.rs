@@ -1,78 +1,360 @@
 #![allow(dead_code)]
 pub mod audio;
+pub mod cli;
 pub mod pathfinding_algorithms;
 pub mod sorting_algorithms;
 
-use std::{env, thread, time::Duration};
+use std::{
+    env,
+    error::Error,
+    io::{self, IsTerminal, Write},
+    process,
+    sync::mpsc::Sender,
+    thread::{self, JoinHandle},
+    time::Duration,
+};
 
 use crate::{
-    audio::AudioDevice,
-    pathfinding_algorithms::{AStar, Dijkstra, Map, BFS},
-    sorting_algorithms::{Heap, MergeSort, QuickSort, SortGraph},
+    audio::{AudioDevice, AudioSignal},
+    cli::{Cli, Command, Options, USAGE},
+    pathfinding_algorithms::{find_pathfinder, pathfinders, Map, Pathfinder},
+    sorting_algorithms::{
+        distribution_names, find_searcher, find_selector_with, find_sorter_with, searchers,
+        selectors, selectors_with, sorters, sorters_with, GapSequence, Heap, HeapOp,
+        PartitionScheme, PivotRule, Searcher, Selector, SortConfig, SortGraph, Sorter,
+    },
 };
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let args: Vec<String> = env::args().collect();
-    let audio_enabled = args.contains(&"audio".to_string());
-    let (mut tx, mut handle) = if audio_enabled {
-        let audio_device = AudioDevice::new();
-        if let Ok(dev) = audio_device {
-            let (tx, handle) = dev.play_audio_live();
-            (Some(tx), Some(handle))
-        } else {
-            panic!();
-            (None, None)
+type Audio = (Option<Sender<AudioSignal>>, Option<JoinHandle<()>>);
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let cli = match Cli::parse(env::args().skip(1)) {
+        Ok(cli) => cli,
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, USAGE);
+            process::exit(2);
         }
-    } else {
-        (None, None)
     };
+    let options = &cli.options;
+
+    match &cli.command {
+        Command::Help => println!("{}", USAGE),
+        Command::List => list_algorithms(),
+        Command::Sort(name) => {
+            let sorter = find_sorter_with(name, &options.sorter_options())
+                .expect("algorithm name checked by the parser");
+            run_sorters(vec![sorter], options)?;
+        }
+        Command::Select(name) => {
+            let selector = find_selector_with(name, &options.sorter_options())
+                .expect("algorithm name checked by the parser");
+            run_selectors(vec![selector], options)?;
+        }
+        Command::Search(name) => {
+            let searcher = find_searcher(name).expect("algorithm name checked by the parser");
+            run_searchers(vec![searcher], options)?;
+        }
+        Command::Path(name) => {
+            let finder = find_pathfinder(name).expect("algorithm name checked by the parser");
+            run_pathfinders(vec![finder], options)?;
+        }
+        Command::Heap => run_priority_queue(options)?,
+        Command::Demo => {
+            run_pathfinders(pathfinders(), options)?;
+            run_sorters(sorters_with(&options.sorter_options()), options)?;
+            run_selectors(selectors_with(&options.sorter_options()), options)?;
+            run_searchers(searchers(), options)?;
+        }
+    }
+
+    Ok(())
+}
 
-    let mut map = Map::new("dijkstra's Algorithm", &mut tx, &mut handle);
+fn list_algorithms() {
+    println!("Sorting algorithms:");
+    for sorter in sorters() {
+        let info = sorter.info();
+        println!(
+            "  {:<20} {:<32} stable: {:<5} in-place: {:<5} best {}, average {}, worst {}, space {}",
+            info.name,
+            info.title,
+            info.stable,
+            info.in_place,
+            info.complexity.best,
+            info.complexity.average,
+            info.complexity.worst,
+            info.complexity.space
+        );
+    }
+    println!("\nSelection algorithms:");
+    for selector in selectors() {
+        let info = selector.info();
+        println!(
+            "  {:<20} {:<32} in-place: {:<5} best {}, average {}, worst {}, space {}",
+            info.name,
+            info.title,
+            info.in_place,
+            info.complexity.best,
+            info.complexity.average,
+            info.complexity.worst,
+            info.complexity.space
+        );
+    }
+    println!("\nSearch algorithms:");
+    for searcher in searchers() {
+        let info = searcher.info();
+        println!(
+            "  {:<20} {:<32} best {}, average {}, worst {}, space {}",
+            info.name,
+            info.title,
+            info.complexity.best,
+            info.complexity.average,
+            info.complexity.worst,
+            info.complexity.space
+        );
+    }
+    println!("\nPathfinding algorithms:");
+    for finder in pathfinders() {
+        println!("  {:<20} {}", finder.name(), finder.title());
+    }
+    println!("\nInputs:\n  {}", distribution_names().join(", "));
+    let gaps: Vec<&str> = GapSequence::ALL.iter().map(|gaps| gaps.name()).collect();
+    println!("\nShell sort gap sequences:\n  {}", gaps.join(", "));
+    let rules: Vec<&str> = PivotRule::ALL.iter().map(|rule| rule.name()).collect();
+    println!("\nQuicksort pivot rules:\n  {}", rules.join(", "));
+    let schemes: Vec<&str> = PartitionScheme::ALL
+        .iter()
+        .map(|scheme| scheme.name())
+        .collect();
+    println!("\nQuicksort partition schemes:\n  {}", schemes.join(", "));
+}
+
+// Starts the live audio thread if audio was requested
+fn start_audio(options: &Options) -> Result<Audio, Box<dyn Error>> {
+    if options.audio && !options.headless {
+        let (tx, handle) = AudioDevice::new()?.play_audio_live();
+        Ok((Some(tx), Some(handle)))
+    } else {
+        Ok((None, None))
+    }
+}
+
+fn run_pathfinders(
+    finders: Vec<Box<dyn Pathfinder>>,
+    options: &Options,
+) -> Result<(), Box<dyn Error>> {
+    let (mut tx, mut handle) = start_audio(options)?;
+    let mut map = Map::with_config("", options.map_config(), &mut tx, &mut handle)?;
+    map.set_headless(options.headless);
+    map.set_speed(options.speed);
+    if let Some(seed) = options.seed {
+        map.set_seed(seed);
+    }
     map.generate();
 
-    Map::reset_cursor();
-    Map::clear_screen();
-    let mut dijkstra = Dijkstra::new(&map);
-    dijkstra.run();
-    map.reset("A*Star Algorithm");
-    let mut astar = AStar::new(&map);
-    let _astar_path = astar.find_path();
-    astar.display_path();
-    map.reset("Breadth First Search Algorithm");
-    let mut bfs = BFS::new(&map);
-    let bfs_path = bfs.run();
-    bfs.display_path(bfs_path);
+    if !options.headless {
+        Map::reset_cursor();
+        Map::clear_screen();
+    }
+    for mut finder in finders {
+        map.reset(finder.title());
+        let result = map.search_with(finder.as_mut());
+        if options.headless {
+            println!(
+                "{}: expanded {} nodes, path cost {:?}",
+                finder.title(),
+                result.expanded.len(),
+                result.cost
+            );
+        }
+    }
+    println!("Seed: {} (replay with --seed {})", map.seed, map.seed);
     map.stop_audio();
     map.join_audio();
+    Ok(())
+}
 
-    let (mut tx, mut handle) = if audio_enabled {
-        let audio_device = AudioDevice::new();
-        let (tx, handle) = audio_device?.play_audio_live();
-        (Some(tx), Some(handle))
-    } else {
-        (None, None)
-    };
-
-    let mut sort_graph = SortGraph::new("Quick Sort Algorithm", &mut tx, &mut handle);
-    let mut quick_sort = QuickSort::new(&mut sort_graph);
-    quick_sort.sort();
+fn run_sorters(sorters: Vec<Box<dyn Sorter>>, options: &Options) -> Result<(), Box<dyn Error>> {
+    let (mut tx, mut handle) = start_audio(options)?;
+    let mut sort_graph = SortGraph::with_config("", options.sort_config(), &mut tx, &mut handle);
+    sort_graph.set_headless(options.headless);
+    sort_graph.set_speed(options.speed);
+    if let Some(seed) = options.seed {
+        sort_graph.set_seed(seed);
+    }
+    let mut summary = Vec::new();
+    for (idx, mut sorter) in sorters.into_iter().enumerate() {
+        if idx > 0 {
+            if !options.headless {
+                thread::sleep(Duration::from_secs_f32(1.0 / options.speed));
+            }
+            sort_graph.reset();
+        }
+        sort_graph.set_title(&sorter.title());
+        sort_graph.sort_with(sorter.as_mut());
+        summary.push((sorter.title(), sort_graph.stats, sort_graph.verification));
+    }
+    for (title, stats, verification) in summary {
+        match verification {
+            Some(verification) => println!("{}: {}, {}", title, stats, verification),
+            None => println!("{}: {}", title, stats),
+        }
+    }
+    println!(
+        "Seed: {} (replay with --seed {})",
+        sort_graph.seed, sort_graph.seed
+    );
     // Stop the audio thread
-    thread::sleep(Duration::from_millis(1000));
-
-    sort_graph.reset();
-    sort_graph.set_title("Merge Sort Algorithm");
-    let mut merge_sort = MergeSort::new(&mut sort_graph);
-    merge_sort.sort();
+    sort_graph.stop_audio();
+    sort_graph.join_audio();
+    Ok(())
+}
 
-    thread::sleep(Duration::from_millis(1000));
+// Runs each selector on the same kind of input, looking for the value of `--rank`
+fn run_selectors(
+    selectors: Vec<Box<dyn Selector>>,
+    options: &Options,
+) -> Result<(), Box<dyn Error>> {
+    let (mut tx, mut handle) = start_audio(options)?;
+    let mut sort_graph = SortGraph::with_config("", options.sort_config(), &mut tx, &mut handle);
+    sort_graph.set_headless(options.headless);
+    sort_graph.set_speed(options.speed);
+    if let Some(seed) = options.seed {
+        sort_graph.set_seed(seed);
+    }
+    let k = options.select_index();
+    let mut summary = Vec::new();
+    for (idx, mut selector) in selectors.into_iter().enumerate() {
+        if idx > 0 {
+            if !options.headless {
+                thread::sleep(Duration::from_secs_f32(1.0 / options.speed));
+            }
+            sort_graph.reset();
+        }
+        sort_graph.set_title(&selector.title());
+        sort_graph.select_with(selector.as_mut(), k);
+        summary.push((selector.title(), sort_graph.stats, sort_graph.selection));
+    }
+    for (title, stats, selection) in summary {
+        match selection {
+            Some(selection) => println!("{}: {}, {}", title, stats, selection),
+            None => println!("{}: {}", title, stats),
+        }
+    }
+    println!(
+        "Seed: {} (replay with --seed {})",
+        sort_graph.seed, sort_graph.seed
+    );
+    sort_graph.stop_audio();
+    sort_graph.join_audio();
+    Ok(())
+}
 
-    sort_graph.reset();
-    sort_graph.set_title("HeapSort Algorithm");
-    let mut heap = Heap::from_graph(&mut sort_graph);
-    heap.heapsort();
-    // Stop the audio thread
+// Runs each searcher on the same kind of input, sorted, looking for `--target` or for a
+// random one of the values
+fn run_searchers(
+    searchers: Vec<Box<dyn Searcher>>,
+    options: &Options,
+) -> Result<(), Box<dyn Error>> {
+    let (mut tx, mut handle) = start_audio(options)?;
+    let mut sort_graph = SortGraph::with_config("", options.sort_config(), &mut tx, &mut handle);
+    sort_graph.set_headless(options.headless);
+    sort_graph.set_speed(options.speed);
+    if let Some(seed) = options.seed {
+        sort_graph.set_seed(seed);
+    }
+    let mut summary = Vec::new();
+    for (idx, mut searcher) in searchers.into_iter().enumerate() {
+        if idx > 0 {
+            if !options.headless {
+                thread::sleep(Duration::from_secs_f32(1.0 / options.speed));
+            }
+            sort_graph.reset();
+        }
+        let target = options.target.unwrap_or_else(|| sort_graph.random_target());
+        sort_graph.set_title(&searcher.title());
+        sort_graph.search_with(searcher.as_mut(), target);
+        summary.push((searcher.title(), sort_graph.stats, sort_graph.search));
+    }
+    for (title, stats, search) in summary {
+        match search {
+            Some(search) => println!(
+                "{}: probes {}, time {:?}, {}",
+                title, stats.probes, stats.elapsed, search
+            ),
+            None => println!(
+                "{}: probes {}, time {:?}",
+                title, stats.probes, stats.elapsed
+            ),
+        }
+    }
+    println!(
+        "Seed: {} (replay with --seed {})",
+        sort_graph.seed, sort_graph.seed
+    );
     sort_graph.stop_audio();
     sort_graph.join_audio();
+    Ok(())
+}
 
+// Runs the heap operations from `--ops`, or typed one line at a time, playing each on the
+// graph. Values given with `--values` are built into the heap first.
+fn run_priority_queue(options: &Options) -> Result<(), Box<dyn Error>> {
+    let (mut tx, mut handle) = start_audio(options)?;
+    let config = SortConfig {
+        values: Some(Vec::new()),
+        ..options.sort_config()
+    };
+    let mut graph = SortGraph::with_config("", config, &mut tx, &mut handle);
+    graph.set_headless(options.headless);
+    graph.set_speed(options.speed);
+    let kind = options.heap_kind;
+    let mut heap = Heap::with_kind(kind);
+    let mut apply = |op: &HeapOp| {
+        graph.set_title(&format!("Priority Queue ({}) - {}", kind.name(), op));
+        let outcome = op.apply(&mut heap);
+        graph.play_operation(&heap.take_trace());
+        match outcome {
+            Ok(outcome) => println!("{}", outcome),
+            Err(err) => println!("{}: {}", op, err),
+        }
+        if !options.headless {
+            thread::sleep(Duration::from_secs_f32(1.0 / options.speed));
+        }
+    };
+
+    if let Some(values) = &options.values {
+        apply(&HeapOp::Build(values.clone()));
+    }
+    match &options.ops {
+        Some(ops) => ops.iter().for_each(&mut apply),
+        None => {
+            let stdin = io::stdin();
+            let interactive = stdin.is_terminal();
+            if interactive {
+                println!("Operations: {}. An empty line quits.", HeapOp::SYNTAX);
+            }
+            loop {
+                if interactive {
+                    print!("heap> ");
+                    io::stdout().flush()?;
+                }
+                let mut line = String::new();
+                if stdin.read_line(&mut line)? == 0 || (interactive && line.trim().is_empty()) {
+                    break;
+                }
+                match HeapOp::parse_script(&line) {
+                    Ok(ops) => ops.iter().for_each(&mut apply),
+                    Err(part) => println!(
+                        "unknown operation '{}', expected one of: {}",
+                        part,
+                        HeapOp::SYNTAX
+                    ),
+                }
+            }
+        }
+    }
+    graph.stop_audio();
+    graph.join_audio();
     Ok(())
 }
@@ -1,8 +1,7 @@
-use crate::pathfinding_algorithms::Map;
+use crate::pathfinding_algorithms::{Map, Pathfinder, SearchResult};
 use std::{
     cmp::{Ordering, Reverse},
     collections::{BinaryHeap, HashMap, HashSet},
-    time::Duration,
 };
 
 // Assuming 'Map' is defined elsewhere and it has 'is_traversable' and 'get_neighbors' methods.
@@ -33,22 +32,25 @@ impl PartialOrd for Node {
     }
 }
 
-pub struct AStar<'a, 'b> {
-    map: &'a Map<'b, 'b>,
-    start: (usize, usize),
-    end: (usize, usize),
-    open_set: BinaryHeap<Reverse<Node>>, // Use Reverse for min-heap behavior
-    came_from: HashMap<(usize, usize), (usize, usize)>,
-    g_score: HashMap<(usize, usize), usize>,
-    visited: HashSet<(usize, usize)>,
-}
+#[derive(Default)]
+pub struct AStar;
+
+impl Pathfinder for AStar {
+    fn name(&self) -> &'static str {
+        "astar"
+    }
 
-impl<'a, 'b> AStar<'a, 'b> {
-    pub fn new(map: &'a Map<'b, 'b>) -> Self {
-        let start = map.start;
-        let end = map.end;
-        let mut open_set = BinaryHeap::new();
+    fn title(&self) -> &'static str {
+        "A*Star Algorithm"
+    }
+
+    fn search(&mut self, map: &Map<'_, '_>) -> SearchResult {
+        let (start, end) = (map.start, map.end);
+        let mut result = SearchResult::default();
+        let mut open_set = BinaryHeap::new(); // Use Reverse for min-heap behavior
+        let mut came_from = HashMap::new();
         let mut g_score = HashMap::new();
+        let mut visited = HashSet::new();
 
         // Initial setup
         g_score.insert(start, 0);
@@ -58,60 +60,49 @@ impl<'a, 'b> AStar<'a, 'b> {
             g_score: 0,
         }));
 
-        AStar {
-            map,
-            start,
-            end,
-            open_set,
-            came_from: HashMap::new(),
-            g_score,
-            visited: HashSet::new(),
-        }
-    }
-
-    pub fn find_path(&mut self) -> Option<Vec<(usize, usize)>> {
-        while let Some(Reverse(current)) = self.open_set.pop() {
-            std::thread::sleep(Duration::from_millis(20));
-            self.display_visited();
-            if current.position == self.end {
-                self.map.update_audio(0.0);
-                return Some(self.reconstruct_path(current.position));
+        while let Some(Reverse(current)) = open_set.pop() {
+            // Skip stale entries for nodes that were already expanded
+            if !visited.insert(current.position) {
+                continue;
+            }
+            result.expanded.push(current.position);
+            if current.position == end {
+                result.frontier.push(Vec::new());
+                result.cost = Some(current.g_score as u32);
+                result.path = Some(Self::reconstruct_path(&came_from, current.position));
+                return result;
             }
 
-            self.visited.insert(current.position);
-            for neighbor in self
-                .map
-                .get_neighbors(current.position.0, current.position.1)
-            {
-                if !self.map.is_traversable(neighbor.0, neighbor.1) {
+            let mut discovered = Vec::new();
+            for neighbor in map.get_neighbors(current.position.0, current.position.1) {
+                if !map.is_traversable(neighbor.0, neighbor.1) {
                     continue;
                 }
 
-                let tentative_g_score = self.g_score[&current.position] + 1; // Assume cost from current to neighbor is 1
+                let tentative_g_score =
+                    g_score[&current.position] + map.cost(current.position, neighbor) as usize;
 
-                if tentative_g_score < *self.g_score.get(&neighbor).unwrap_or(&usize::MAX) {
-                    self.came_from.insert(neighbor, current.position);
-                    self.g_score.insert(neighbor, tentative_g_score);
-                    let f_score = tentative_g_score + Self::heuristic(neighbor, self.end);
-                    self.open_set.push(Reverse(Node {
+                if tentative_g_score < *g_score.get(&neighbor).unwrap_or(&usize::MAX) {
+                    came_from.insert(neighbor, current.position);
+                    g_score.insert(neighbor, tentative_g_score);
+                    let f_score = tentative_g_score + Self::heuristic(neighbor, end);
+                    open_set.push(Reverse(Node {
                         position: neighbor,
                         f_score,
                         g_score: tentative_g_score,
                     }));
-                    self.map.play_distance(tentative_g_score as u32, neighbor);
+                    discovered.push(neighbor);
                 }
             }
+            result.frontier.push(discovered);
         }
-        self.map.update_audio(0.0);
-        None
+        result
     }
-    pub fn display_visited(&self) {
-        let visited = &self.visited;
-        self.map.display_visited(visited);
-    }
-    pub fn display_path(&self) {
-        let path = self.reconstruct_path(self.end);
-        self.map.display_path(&path);
+}
+
+impl AStar {
+    pub fn new() -> Self {
+        AStar
     }
 
     fn heuristic(start: (usize, usize), end: (usize, usize)) -> usize {
@@ -120,10 +111,13 @@ impl<'a, 'b> AStar<'a, 'b> {
             as usize
     }
 
-    fn reconstruct_path(&self, mut current: (usize, usize)) -> Vec<(usize, usize)> {
+    fn reconstruct_path(
+        came_from: &HashMap<(usize, usize), (usize, usize)>,
+        mut current: (usize, usize),
+    ) -> Vec<(usize, usize)> {
         let mut path = Vec::new();
         path.push(current);
-        while let Some(&next) = self.came_from.get(&current) {
+        while let Some(&next) = came_from.get(&current) {
             path.push(next);
             current = next;
         }
@@ -1,73 +1,71 @@
-use crate::pathfinding_algorithms::Map;
+use crate::pathfinding_algorithms::{Map, Pathfinder, SearchResult};
 use std::collections::{HashMap, HashSet, VecDeque};
 
-pub struct BFS<'a, 'b> {
-    pub map: &'a Map<'b, 'b>,
-    start: (usize, usize),
-    end: (usize, usize),
-    visited: HashSet<(usize, usize)>,
-    queue: VecDeque<(usize, usize)>,
-    parent: HashMap<(usize, usize), (usize, usize)>, // To track the path
-}
+#[derive(Default)]
+pub struct BFS;
+
+impl Pathfinder for BFS {
+    fn name(&self) -> &'static str {
+        "bfs"
+    }
 
-impl<'a, 'b> BFS<'a, 'b> {
-    pub fn new(map: &'a Map<'b, 'b>) -> Self {
-        let start = map.start;
-        let end = map.end;
-        let mut bfs = BFS {
-            map,
-            start,
-            end,
-            visited: HashSet::new(),
-            queue: VecDeque::new(),
-            parent: HashMap::new(),
-        };
-        bfs.queue.push_back(start);
-        bfs.visited.insert(start);
-        bfs.parent.insert(start, start); // Initialize the parent of the start node to itself
-        bfs
+    fn title(&self) -> &'static str {
+        "Breadth First Search Algorithm"
     }
 
-    pub fn run(&mut self) -> Option<Vec<(usize, usize)>> {
-        while let Some(current) = self.queue.pop_front() {
-            self.display_visited();
-            if current == self.end {
-                self.map.update_audio(0.0);
-                return Some(self.get_path(self.end));
+    fn search(&mut self, map: &Map<'_, '_>) -> SearchResult {
+        let (start, end) = (map.start, map.end);
+        let mut result = SearchResult::default();
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::new();
+        let mut parent = HashMap::new(); // To track the path
+
+        queue.push_back(start);
+        visited.insert(start);
+        parent.insert(start, start); // Initialize the parent of the start node to itself
+
+        while let Some(current) = queue.pop_front() {
+            result.expanded.push(current);
+            if current == end {
+                let path = Self::get_path(&parent, end);
+                result.cost = Some(path.windows(2).map(|w| map.cost(w[0], w[1])).sum());
+                result.path = Some(path);
+                result.frontier.push(Vec::new());
+                return result;
             }
-            for neighbor in self.map.get_neighbors(current.0, current.1) {
-                if !self.visited.contains(&neighbor) {
-                    self.queue.push_back(neighbor);
-                    self.visited.insert(neighbor);
-                    self.parent.insert(neighbor, current); // Track where we came from
-                    let dist =
-                        Map::manhattan_distance(neighbor.0, neighbor.1, self.end.0, self.end.1);
-                    self.map.play_distance(dist as u32, neighbor);
+            let mut discovered = Vec::new();
+            for neighbor in map.get_neighbors(current.0, current.1) {
+                if !visited.contains(&neighbor) {
+                    queue.push_back(neighbor);
+                    visited.insert(neighbor);
+                    parent.insert(neighbor, current); // Track where we came from
+                    discovered.push(neighbor);
                 }
             }
+            result.frontier.push(discovered);
         }
-        None // Return None if the end is not reachable
+        result // No path if the end is not reachable
     }
-    pub fn display_visited(&self) {
-        let visited = &self.visited;
-        self.map.display_visited(visited);
-    }
-    pub fn display_path(&mut self, path: Option<Vec<(usize, usize)>>) {
-        if let Some(p) = path {
-            self.map.display_path(&p);
-        }
+}
+
+impl BFS {
+    pub fn new() -> Self {
+        BFS
     }
 
-    fn get_path(&self, mut current: (usize, usize)) -> Vec<(usize, usize)> {
+    fn get_path(
+        parent: &HashMap<(usize, usize), (usize, usize)>,
+        mut current: (usize, usize),
+    ) -> Vec<(usize, usize)> {
         let mut path = Vec::new();
         // Follow the parent nodes from the end to the start
-        while let Some(&parent) = self.parent.get(&current) {
+        while let Some(&prev) = parent.get(&current) {
             path.push(current);
-            if current == parent {
+            if current == prev {
                 // Stop if the current node is the start node
                 break;
             }
-            current = parent;
+            current = prev;
         }
         path.reverse(); // The path is constructed backwards, so reverse it
         path
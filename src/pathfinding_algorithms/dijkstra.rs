@@ -1,18 +1,25 @@
-use crate::pathfinding_algorithms::Map;
+use crate::pathfinding_algorithms::{Map, Pathfinder, SearchResult};
 use std::collections::{BinaryHeap, HashMap, HashSet};
-#[derive(Debug)]
-pub struct Dijkstra<'a, 'b> {
-    map: &'a Map<'b, 'b>,
-    distances: HashMap<(usize, usize), u32>,
-    priority_queue: BinaryHeap<(i32, (usize, usize))>, // Use negative cost for max-heap behavior
-    visited: HashSet<(usize, usize)>,
-    predecessors: HashMap<(usize, usize), (usize, usize)>, // Store each node's predecessor
-}
 
-impl<'a, 'b> Dijkstra<'a, 'b> {
-    pub fn new(map: &'a Map<'b, 'b>) -> Self {
+#[derive(Debug, Default)]
+pub struct Dijkstra;
+
+impl Pathfinder for Dijkstra {
+    fn name(&self) -> &'static str {
+        "dijkstra"
+    }
+
+    fn title(&self) -> &'static str {
+        "Dijkstra's Algorithm"
+    }
+
+    /// Runs Dijkstra's algorithm to find the shortest path from the start to the end
+    fn search(&mut self, map: &Map<'_, '_>) -> SearchResult {
+        let mut result = SearchResult::default();
         let mut distances = HashMap::new();
-        let mut priority_queue = BinaryHeap::new();
+        let mut priority_queue = BinaryHeap::new(); // Use negative cost for max-heap behavior
+        let mut visited = HashSet::new();
+        let mut predecessors = HashMap::new(); // Store each node's predecessor
 
         // Initialize distances to a very high value (infinity)
         for y in 0..map.height {
@@ -25,78 +32,62 @@ impl<'a, 'b> Dijkstra<'a, 'b> {
         distances.insert(map.start, 0);
         priority_queue.push((0, map.start)); // Start with the start node
 
-        Dijkstra {
-            map,
-            distances,
-            priority_queue,
-            visited: HashSet::new(),
-            predecessors: HashMap::new(),
-        }
-    }
-
-    /// Runs the Dijkstra's algorithm to find the shortest path from the start to the end
-    pub fn run(&mut self) {
-        self.map.display();
-        while let Some((current_distance, current_position)) = self.priority_queue.pop() {
-            self.display_visited();
-
+        while let Some((current_distance, current_position)) = priority_queue.pop() {
             let current_distance = -current_distance as u32; // Convert back to positive
 
-            self.visited.insert(current_position);
-            // Early exit if we reached the end point
-            if current_position == self.map.end {
-                break;
+            // Skip stale queue entries for nodes already reached by a cheaper path
+            if !visited.insert(current_position)
+                || current_distance > *distances.get(&current_position).unwrap()
+            {
+                continue;
             }
+            result.expanded.push(current_position);
 
-            // If we found a better path to the current node, continue processing
-            if current_distance > *self.distances.get(&current_position).unwrap() {
-                continue;
+            // Early exit if we reached the end point
+            if current_position == map.end {
+                result.frontier.push(Vec::new());
+                result.cost = Some(current_distance);
+                result.path = Some(Self::get_path(map, &predecessors));
+                return result;
             }
 
             // Check each neighbor
-            for neighbor in self
-                .map
-                .get_neighbors(current_position.0, current_position.1)
-            {
-                let next = neighbor;
-                let new_cost = current_distance + self.map.cost(current_position, next);
+            let mut discovered = Vec::new();
+            for next in map.get_neighbors(current_position.0, current_position.1) {
+                let new_cost = current_distance + map.cost(current_position, next);
 
-                if new_cost < *self.distances.get(&next).unwrap() {
+                if new_cost < *distances.get(&next).unwrap() {
                     // Found a better way to this neighbor
-                    self.distances.insert(next, new_cost);
-                    self.predecessors.insert(next, current_position); // Update the predecessor
-                    self.priority_queue.push((-(new_cost as i32), next)); // Push new cost as negative
-                    self.map.play_distance(current_distance, current_position);
+                    distances.insert(next, new_cost);
+                    predecessors.insert(next, current_position); // Update the predecessor
+                    priority_queue.push((-(new_cost as i32), next)); // Push new cost as negative
+                    discovered.push(next);
                 }
             }
+            result.frontier.push(discovered);
         }
-        self.display_visited();
-        self.map.update_audio(0.0);
-        self.display_path();
+        result
     }
-    pub fn display_visited(&self) {
-        let visited = &self.visited;
-        self.map.display_visited(visited);
-    }
-    pub fn display_path(&self) {
-        let path = self.get_path();
-        self.map.display_path(&path)
+}
+
+impl Dijkstra {
+    pub fn new() -> Self {
+        Dijkstra
     }
 
-    pub fn get_path(&self) -> Vec<(usize, usize)> {
+    fn get_path(
+        map: &Map<'_, '_>,
+        predecessors: &HashMap<(usize, usize), (usize, usize)>,
+    ) -> Vec<(usize, usize)> {
         let mut path = Vec::new();
-        let mut step = self.map.end;
-
-        if !self.predecessors.contains_key(&step) {
-            return vec![]; // If there's no path to the end, return empty vector
-        }
+        let mut step = map.end;
 
-        while step != self.map.start {
+        while step != map.start {
             path.push(step);
-            step = *self.predecessors.get(&step).unwrap(); // Retrieve the predecessor of the current step
+            step = *predecessors.get(&step).unwrap(); // Retrieve the predecessor of the current step
         }
 
-        path.push(self.map.start); // Add the start position at the end
+        path.push(map.start); // Add the start position at the end
         path.reverse(); // Reverse to show path from start to end
         path
     }
@@ -6,6 +6,8 @@ use crossterm::{
 
 use std::{
     collections::{HashSet, VecDeque},
+    error::Error,
+    fmt,
     io::Write,
     sync::mpsc::Sender,
     thread,
@@ -13,12 +15,94 @@ use std::{
     time::Duration,
 }; // Crossterm handles cursor movement and more
 
-use crate::audio::AudioSignal;
+use crate::{
+    audio::AudioSignal,
+    pathfinding_algorithms::{Pathfinder, SearchResult},
+};
 use colored::{ColoredString, Colorize};
-use rand::{seq::SliceRandom, thread_rng, Rng};
+use rand::{rngs::StdRng, seq::SliceRandom, thread_rng, Rng, SeedableRng};
+
+/// Where a start or end square is placed when the map is generated.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Placement {
+    Fixed(usize, usize),
+    Random,
+}
+
+/// Describes the map `generate` builds.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct MapConfig {
+    pub width: usize,
+    pub height: usize,
+    pub start: Placement,
+    pub end: Placement,
+    pub min_distance: usize, // Smallest Manhattan distance between randomly placed start and end
+}
+
+impl Default for MapConfig {
+    fn default() -> Self {
+        Self::new(30, 30)
+    }
+}
+
+impl MapConfig {
+    /// A `width` by `height` map starting in the top-left corner, with the end placed randomly
+    /// at least a quarter of the way around the map away.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            start: Placement::Fixed(0, 0),
+            end: Placement::Random,
+            min_distance: (width + height) / 4,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), MapError> {
+        if self.width < 2 || self.height < 2 {
+            return Err(MapError::TooSmall(self.width, self.height));
+        }
+        for (name, placement) in [("start", self.start), ("end", self.end)] {
+            if let Placement::Fixed(x, y) = placement {
+                if x >= self.width || y >= self.height {
+                    return Err(MapError::OutOfBounds(name, (x, y)));
+                }
+            }
+        }
+        if self.start != Placement::Random && self.start == self.end {
+            return Err(MapError::SameStartAndEnd);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum MapError {
+    TooSmall(usize, usize),
+    OutOfBounds(&'static str, (usize, usize)),
+    SameStartAndEnd,
+}
+
+impl fmt::Display for MapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooSmall(width, height) => {
+                write!(
+                    f,
+                    "a {}x{} map is too small, it needs at least 2x2",
+                    width, height
+                )
+            }
+            Self::OutOfBounds(name, (x, y)) => {
+                write!(f, "the {} square ({}, {}) is outside the map", name, x, y)
+            }
+            Self::SameStartAndEnd => write!(f, "the start and end squares are the same"),
+        }
+    }
+}
+
+impl Error for MapError {}
 
-const WIDTH: usize = 30;
-const HEIGHT: usize = 30;
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum Direction {
     Left,
@@ -27,18 +111,26 @@ pub enum Direction {
     Down,
 }
 
+// Fresh mazes to try before opening a passage to the end by hand
+const GENERATE_ATTEMPTS: usize = 10;
+
 #[derive(Debug)]
 pub struct Map<'a, 'b> {
     pub graph_title: String,
     pub graph: Vec<Vec<u8>>, // Using a Vec<Vec<u8>> for simplicity, 0 is non-traversable, 1 is traversable
     pub width: usize,
     pub height: usize,
+    pub config: MapConfig,
     pub current: (usize, usize),
     pub visited: Vec<(usize, usize)>,
     pub start: (usize, usize), // Coordinates for the start square
     pub end: (usize, usize),   // Coordinates for the end square
     pub audio_sender: &'a mut Option<Sender<AudioSignal>>, // Audio sender for live updates
     pub audio_handle: &'b mut Option<JoinHandle<()>>, // Audio thread handle
+    pub headless: bool,        // Skip all drawing, audio and delays
+    pub speed: f32,            // Animation speed multiplier
+    pub seed: u64,             // Seed of `rng`, so runs can be replayed
+    rng: StdRng,
 }
 
 impl<'a, 'b> Map<'a, 'b> {
@@ -47,37 +139,115 @@ impl<'a, 'b> Map<'a, 'b> {
         audio_sender: &'a mut Option<Sender<AudioSignal>>,
         audio_handle: &'b mut Option<JoinHandle<()>>,
     ) -> Self {
-        let mut rng = thread_rng(); // Get a random number generator
-
-        // Define the map dimensions
-        let (width, height) = (WIDTH, HEIGHT);
-
-        // Define the start position
-        let start = (0, 0);
+        Self::with_config(title, MapConfig::default(), audio_sender, audio_handle)
+            .expect("the default map config is valid")
+    }
+    /// Creates a map laid out according to `config`. Call `generate` to carve the maze.
+    pub fn with_config(
+        title: &str,
+        config: MapConfig,
+        audio_sender: &'a mut Option<Sender<AudioSignal>>,
+        audio_handle: &'b mut Option<JoinHandle<()>>,
+    ) -> Result<Self, MapError> {
+        config.validate()?;
+        let seed = thread_rng().gen();
 
-        // Randomly choose to place 'end' based on width or height
-        let (end_x, end_y) = if rng.gen::<bool>() {
-            // Randomly choose 'end' beyond half the width, ensure it is not in the first row
-            (rng.gen_range(width / 2..width), rng.gen_range(1..height))
-        } else {
-            // Randomly choose 'end' beyond half the height, ensure it is not in the first column
-            (rng.gen_range(1..width), rng.gen_range(height / 2..height))
-        };
-        Self::clear_screen();
-        Self::reset_cursor();
         // Create the map
-        Map {
+        let mut map = Map {
             graph_title: title.to_string(),
-            graph: vec![vec![0; width]; height], // Initialize all cells as non-traversable
-            width,
-            height,
-            current: start,       // Set current position to start
-            visited: vec![start], // Start has been visited
-            start,
-            end: (end_x, end_y), // Set the random 'end' position
+            graph: Vec::new(),
+            width: config.width,
+            height: config.height,
+            config,
+            current: (0, 0),
+            visited: Vec::new(),
+            start: (0, 0),
+            end: (0, 0),
             audio_sender,
             audio_handle,
-        }
+            headless: false,
+            speed: 1.0,
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        };
+        map.place_endpoints();
+        Ok(map)
+    }
+    /// Switches to a new layout. Call `generate` afterwards to carve a new maze.
+    pub fn set_config(&mut self, config: MapConfig) -> Result<(), MapError> {
+        config.validate()?;
+        self.config = config;
+        self.width = config.width;
+        self.height = config.height;
+        self.place_endpoints();
+        Ok(())
+    }
+    /// Reseeds the random number generator and places the start and end from it.
+    /// Call `generate` afterwards to carve a new maze.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = StdRng::seed_from_u64(seed);
+        self.place_endpoints();
+    }
+    // Clears the grid and places the start and end squares as the config asks
+    fn place_endpoints(&mut self) {
+        let (width, height) = (self.width, self.height);
+        self.graph = vec![vec![0; width]; height]; // Initialize all cells as non-traversable
+        self.start = match self.config.start {
+            Placement::Fixed(x, y) => (x, y),
+            Placement::Random => self.random_start(),
+        };
+        self.end = match self.config.end {
+            Placement::Fixed(x, y) => (x, y),
+            Placement::Random => self.random_end(),
+        };
+        self.current = self.start; // Set current position to start
+        self.visited = vec![self.start]; // Start has been visited
+    }
+    // Picks a random square other than a fixed end
+    fn random_start(&mut self) -> (usize, usize) {
+        let end = match self.config.end {
+            Placement::Fixed(x, y) => Some((x, y)),
+            Placement::Random => None,
+        };
+        let squares: Vec<(usize, usize)> = (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .filter(|&square| Some(square) != end)
+            .collect();
+        *squares
+            .choose(&mut self.rng)
+            .expect("a map of at least 2x2 has a square other than the end")
+    }
+    // Picks a random square at least `min_distance` away from the start, or one of the
+    // farthest squares when the map is too small for that
+    fn random_end(&mut self) -> (usize, usize) {
+        let start = self.start;
+        let distance = |(x, y): (usize, usize)| Self::manhattan_distance(x, y, start.0, start.1);
+        let squares: Vec<(usize, usize)> = (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .filter(|&square| square != start)
+            .collect();
+        let farthest = squares.iter().map(|&square| distance(square)).max();
+        let min_distance = self.config.min_distance.min(farthest.unwrap_or(0));
+        let candidates: Vec<(usize, usize)> = squares
+            .into_iter()
+            .filter(|&square| distance(square) >= min_distance)
+            .collect();
+        *candidates
+            .choose(&mut self.rng)
+            .expect("a map of at least 2x2 has a square other than the start")
+    }
+    /// Turns off drawing, audio and delays. Searches still return their full result.
+    pub fn set_headless(&mut self, headless: bool) {
+        self.headless = headless;
+    }
+    /// Sets the animation speed multiplier, 2.0 plays twice as fast.
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed;
+    }
+    // Scales a delay by the animation speed
+    fn delay(&self, millis: u64) -> Duration {
+        Duration::from_secs_f32(millis as f32 / 1000.0 / self.speed)
     }
     pub fn update_audio(&self, frequency: f32) {
         if let Some(ref sender) = self.audio_sender {
@@ -99,17 +269,23 @@ impl<'a, 'b> Map<'a, 'b> {
         }
     }
     pub fn play_visited(&self, distances: Vec<usize>, duration: u64) {
+        if self.headless {
+            return;
+        }
         if let Some(sender) = &self.audio_sender {
             for distance in distances.iter() {
                 let freq = 440.0 + (440.0 * (1.0 - *distance as f32 / self.height as f32));
                 sender.send(AudioSignal::Single(freq)).unwrap_or_default();
                 let duration = duration / distances.len() as u64;
-                thread::sleep(Duration::from_millis(duration));
+                thread::sleep(self.delay(duration));
                 sender.send(AudioSignal::Single(0.0)).unwrap_or_default();
             }
         }
     }
     pub fn play_distance(&self, distance: u32, position: (usize, usize)) {
+        if self.headless {
+            return;
+        }
         let (x, y) = (position.0 as f32, position.1 as f32);
         if let Some(sender) = &self.audio_sender {
             let freq =
@@ -119,10 +295,13 @@ impl<'a, 'b> Map<'a, 'b> {
             sender
                 .send(AudioSignal::Chord(vec![freq, freq_y, freq_x]))
                 .unwrap_or_default();
-            thread::sleep(Duration::from_millis(30));
+            thread::sleep(self.delay(30));
         }
     }
     pub fn play_end_location(&self) {
+        if self.headless {
+            return;
+        }
         if let Some(sender) = &self.audio_sender {
             let distance = 0.;
             let freq = 440.0 + (440.0 * (1.0 - distance as f32 / (self.end.1 * self.end.0) as f32));
@@ -131,7 +310,7 @@ impl<'a, 'b> Map<'a, 'b> {
             sender
                 .send(AudioSignal::Chord(vec![freq, freq_y, freq_x]))
                 .unwrap_or_default();
-            thread::sleep(Duration::from_millis(1000));
+            thread::sleep(self.delay(1000));
         }
     }
 
@@ -141,20 +320,28 @@ impl<'a, 'b> Map<'a, 'b> {
         self.display();
     }
     pub fn generate(&mut self) {
-        loop {
+        for _ in 0..GENERATE_ATTEMPTS {
             // Attempt to generate the map
             self.attempt_generate();
 
             // Check if there's a path from start to end
             if self.is_path_from_start_to_end() {
-                break; // Break the loop if a valid path exists
+                return; // Stop if a valid path exists
             }
         }
+        self.connect_end();
+    }
+    // The maze is carved two squares at a time from the start, so every square whose
+    // coordinates share the start's parity is reachable. An end differing in both can be walled
+    // in, so open the neighbor between it and the carved squares above or below that neighbor.
+    fn connect_end(&mut self) {
+        let (x, y) = self.end;
+        let nx = if x > 0 { x - 1 } else { x + 1 };
+        self.graph[y][nx] = 1;
     }
     // Generates a maze-like map
     pub fn attempt_generate(&mut self) {
         let (_width, _height) = (self.width, self.height);
-        let mut rng = rand::thread_rng();
 
         // Initialize all cells as walls
         for row in self.graph.iter_mut() {
@@ -173,7 +360,7 @@ impl<'a, 'b> Map<'a, 'b> {
         while let Some((cx, cy)) = stack.pop() {
             // Shuffle directions for randomness
             let mut shuffled_directions = directions;
-            shuffled_directions.shuffle(&mut rng);
+            shuffled_directions.shuffle(&mut self.rng);
 
             for &(dx, dy) in &shuffled_directions {
                 let nx = cx + 2 * dx;
@@ -237,6 +424,9 @@ impl<'a, 'b> Map<'a, 'b> {
     }
     // Prints the map for visualization
     pub fn display(&self) {
+        if self.headless {
+            return;
+        }
         let mut buffer = String::new();
         // Hide the cursor to avoid flickering
         buffer.push_str("\x1B[?25l");
@@ -274,10 +464,13 @@ impl<'a, 'b> Map<'a, 'b> {
 
         // Print the entire buffer at once to the terminal
         println!("{}", buffer);
-        thread::sleep(Duration::from_millis(2000))
+        thread::sleep(self.delay(2000))
     }
 
     pub fn display_visited(&self, path: &HashSet<(usize, usize)>) {
+        if self.headless {
+            return;
+        }
         let mut buffer = String::new();
         // Hide the cursor to avoid flickering
         buffer.push_str("\x1B[?25l");
@@ -313,9 +506,37 @@ impl<'a, 'b> Map<'a, 'b> {
 
         // Print the entire buffer at once to the terminal
         print!("{}", buffer);
-        std::thread::sleep(Duration::from_millis(5));
+        std::thread::sleep(self.delay(5));
+    }
+    /// Runs `finder` on the map and replays the search on screen.
+    pub fn search_with(&self, finder: &mut dyn Pathfinder) -> SearchResult {
+        let result = finder.search(self);
+        self.play_search(&result);
+        result
+    }
+    /// Replays a finished search one expansion at a time, then draws the path if one was found.
+    pub fn play_search(&self, result: &SearchResult) {
+        if self.headless {
+            return;
+        }
+        let mut visited = HashSet::new();
+        for (current, discovered) in result.expanded.iter().zip(result.frontier.iter()) {
+            visited.insert(*current);
+            self.display_visited(&visited);
+            for &(x, y) in discovered {
+                let dist = Self::manhattan_distance(x, y, self.end.0, self.end.1);
+                self.play_distance(dist as u32, (x, y));
+            }
+        }
+        self.update_audio(0.0);
+        if let Some(path) = &result.path {
+            self.display_path(path);
+        }
     }
     pub fn display_path(&self, path: &Vec<(usize, usize)>) {
+        if self.headless {
+            return;
+        }
         let mut stdout = std::io::stdout();
         // Save the current cursor position
         stdout.execute(SavePosition).unwrap();
@@ -345,7 +566,7 @@ impl<'a, 'b> Map<'a, 'b> {
             stdout.execute(Print(content)).unwrap();
 
             // Sleep to visually demonstrate the update
-            std::thread::sleep(Duration::from_millis(20)); // Adjust sleep duration as needed
+            std::thread::sleep(self.delay(20)); // Adjust sleep duration as needed
 
             // Flush stdout to ensure the update is shown immediately
             stdout.flush().unwrap();
@@ -361,7 +582,7 @@ impl<'a, 'b> Map<'a, 'b> {
         // Flush to ensure all commands take effect
         stdout.flush().unwrap();
         self.update_audio(0.0);
-        sleep(Duration::from_millis(1000));
+        sleep(self.delay(1000));
     }
     pub fn is_traversable(&self, x: usize, y: usize) -> bool {
         self.graph[y][x] == 1
@@ -391,3 +612,28 @@ impl<'a, 'b> Map<'a, 'b> {
         1 // Uniform cost; adjust as necessary for different terrains or obstacles
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn random_endpoints_avoid_the_fixed_one() {
+        let mut configs = [MapConfig::new(2, 2), MapConfig::new(2, 2)];
+        configs[0].start = Placement::Random;
+        configs[0].end = Placement::Fixed(1, 1);
+        configs[1].start = Placement::Fixed(1, 1);
+        configs[1].end = Placement::Random;
+        for config in configs {
+            let (mut audio_sender, mut audio_handle) = (None, None);
+            let mut map =
+                Map::with_config("", config, &mut audio_sender, &mut audio_handle).unwrap();
+            for seed in 0..100 {
+                map.set_seed(seed);
+                assert_ne!(map.start, map.end, "{:?} with seed {}", config, seed);
+                assert_eq!(map.start == (1, 1), config.start != Placement::Random);
+                assert_eq!(map.end == (1, 1), config.end != Placement::Random);
+            }
+        }
+    }
+}
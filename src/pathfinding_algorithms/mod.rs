@@ -3,8 +3,10 @@ pub mod astar;
 pub mod bfs;
 pub mod dijkstra;
 pub mod map;
+pub mod pathfinder;
 
 pub use astar::AStar;
 pub use bfs::BFS;
 pub use dijkstra::Dijkstra;
-pub use map::Map;
+pub use map::{Map, MapConfig, MapError, Placement};
+pub use pathfinder::{find_pathfinder, pathfinder_names, pathfinders, Pathfinder, SearchResult};
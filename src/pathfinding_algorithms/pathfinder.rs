@@ -0,0 +1,105 @@
+use crate::pathfinding_algorithms::{AStar, Dijkstra, Map, BFS};
+
+/// Outcome of running a pathfinding algorithm on a `Map`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchResult {
+    pub path: Option<Vec<(usize, usize)>>, // Start to end inclusive, None if the end is unreachable
+    pub cost: Option<u32>,                 // Total cost of `path` according to `Map::cost`
+    pub expanded: Vec<(usize, usize)>,     // Nodes in the order they were expanded
+    pub frontier: Vec<Vec<(usize, usize)>>, // Nodes added to the frontier by each expansion
+}
+
+impl SearchResult {
+    pub fn found(&self) -> bool {
+        self.path.is_some()
+    }
+}
+
+/// Common interface implemented by every pathfinding algorithm.
+pub trait Pathfinder {
+    /// Registry key used to pick the algorithm at runtime.
+    fn name(&self) -> &'static str;
+
+    /// Title shown above the map.
+    fn title(&self) -> &'static str;
+
+    /// Searches for a path from `map.start` to `map.end`.
+    fn search(&mut self, map: &Map<'_, '_>) -> SearchResult;
+}
+
+/// Returns one instance of every available pathfinding algorithm.
+pub fn pathfinders() -> Vec<Box<dyn Pathfinder>> {
+    vec![
+        Box::new(Dijkstra::new()),
+        Box::new(AStar::new()),
+        Box::new(BFS::new()),
+    ]
+}
+
+/// Looks up a pathfinding algorithm by its registry name, ignoring case.
+pub fn find_pathfinder(name: &str) -> Option<Box<dyn Pathfinder>> {
+    pathfinders()
+        .into_iter()
+        .find(|finder| finder.name().eq_ignore_ascii_case(name.trim()))
+}
+
+/// Returns the registry names of every available pathfinding algorithm.
+pub fn pathfinder_names() -> Vec<&'static str> {
+    pathfinders().iter().map(|finder| finder.name()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pathfinding_algorithms::{MapConfig, Placement};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn pathfinders_find_valid_paths_of_equal_cost() {
+        let mut rng = StdRng::seed_from_u64(11);
+        for seed in 0..60 {
+            let mut config = MapConfig::new(rng.gen_range(2..=25), rng.gen_range(2..=25));
+            if rng.gen() {
+                config.start = Placement::Random;
+            }
+            let (mut audio_sender, mut audio_handle) = (None, None);
+            let mut map =
+                Map::with_config("", config, &mut audio_sender, &mut audio_handle).unwrap();
+            map.set_headless(true);
+            map.set_seed(seed);
+            map.generate();
+
+            let mut costs = Vec::new();
+            for mut finder in pathfinders() {
+                let result = finder.search(&map);
+                let context = format!("{} on {:?} with seed {}", finder.name(), config, seed);
+                let path = result.path.expect(&context);
+                assert_eq!(path.first(), Some(&map.start), "{}", context);
+                assert_eq!(path.last(), Some(&map.end), "{}", context);
+                assert!(
+                    path.iter().all(|&(x, y)| map.is_traversable(x, y)),
+                    "{}",
+                    context
+                );
+                assert!(
+                    path.windows(2).all(|step| {
+                        let ((x1, y1), (x2, y2)) = (step[0], step[1]);
+                        Map::manhattan_distance(x1, y1, x2, y2) == 1
+                    }),
+                    "{}",
+                    context
+                );
+                let path_cost = path.windows(2).map(|step| map.cost(step[0], step[1])).sum();
+                assert_eq!(result.cost, Some(path_cost), "{}", context);
+                costs.push(path_cost);
+            }
+            assert!(
+                costs.windows(2).all(|pair| pair[0] == pair[1]),
+                "costs {:?} on {:?} with seed {}",
+                costs,
+                config,
+                seed
+            );
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use crate::sorting_algorithms::{Complexity, SortInfo, SortRecorder, Sorter};
+use std::cmp::Ordering;
+
+#[derive(Default)]
+pub struct BinaryInsertionSort;
+
+impl Sorter for BinaryInsertionSort {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "binaryinsertionsort",
+            title: "Binary Insertion Sort Algorithm",
+            stable: true,
+            in_place: true,
+            complexity: Complexity {
+                best: "O(n log n)",
+                average: "O(n^2)",
+                worst: "O(n^2)",
+                space: "O(1)",
+            },
+        }
+    }
+
+    fn sort(&mut self, array: &mut SortRecorder) {
+        for i in 1..array.len() {
+            array.sorted(0, i);
+            Self::insert(array, 0, i);
+        }
+        array.sorted(0, array.len());
+    }
+}
+
+impl BinaryInsertionSort {
+    pub fn new() -> Self {
+        BinaryInsertionSort
+    }
+    /// Extends the sorted range `start..sorted_end` to cover `start..end`, one value at a time.
+    pub fn extend_sorted(array: &mut SortRecorder, start: usize, sorted_end: usize, end: usize) {
+        for i in sorted_end.max(start + 1)..end {
+            Self::insert(array, start, i);
+        }
+    }
+    // Moves the value at `i` into place in the sorted range `start..i`
+    fn insert(array: &mut SortRecorder, start: usize, i: usize) {
+        array.current(i);
+        let key = array[i];
+        let slot = Self::upper_bound(array, start, i, key);
+        // Shift the values after the slot right by one and drop the key in
+        for hole in (slot + 1..=i).rev() {
+            array.write(hole, array[hole - 1]);
+            array.current(hole - 1);
+        }
+        array.write(slot, key);
+    }
+    /// Returns the first index in the sorted range `start..end` holding a value greater than
+    /// `key`, so equal values keep their order.
+    fn upper_bound(array: &mut SortRecorder, start: usize, end: usize, key: i32) -> usize {
+        let (mut low, mut high) = (start, end);
+        while low < high {
+            let middle = (low + high) / 2;
+            if array.compare_values(middle, array[middle], end, key) == Ordering::Greater {
+                high = middle;
+            } else {
+                low = middle + 1;
+            }
+        }
+        low
+    }
+}
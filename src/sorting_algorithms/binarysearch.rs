@@ -0,0 +1,53 @@
+use crate::sorting_algorithms::{Complexity, SearchInfo, Searcher, SortRecorder};
+use std::cmp::Ordering;
+
+/// Binary search. Each probe is the middle of the range the target can still be in, and
+/// rules out the half on the wrong side of it.
+#[derive(Default)]
+pub struct BinarySearch;
+
+impl Searcher for BinarySearch {
+    fn info(&self) -> SearchInfo {
+        SearchInfo {
+            name: "binarysearch",
+            title: "Binary Search Algorithm",
+            complexity: Complexity {
+                best: "O(1)",
+                average: "O(log n)",
+                worst: "O(log n)",
+                space: "O(1)",
+            },
+        }
+    }
+
+    fn search(&mut self, array: &mut SortRecorder, target: i32) -> Option<usize> {
+        let len = array.len();
+        Self::search_range(array, target, 0, len)
+    }
+}
+
+impl BinarySearch {
+    pub fn new() -> Self {
+        BinarySearch
+    }
+    /// Binary searches `low..high` for `target`, so other searches can finish with it once
+    /// they have narrowed the range down.
+    pub fn search_range(
+        array: &mut SortRecorder,
+        target: i32,
+        mut low: usize,
+        mut high: usize,
+    ) -> Option<usize> {
+        while low < high {
+            array.focus(low, high);
+            let middle = low + (high - low) / 2;
+            match array.probe(middle, target) {
+                Ordering::Less => low = middle + 1,
+                Ordering::Greater => high = middle,
+                Ordering::Equal => return Some(middle),
+            }
+        }
+        array.focus(low, high);
+        None
+    }
+}
@@ -0,0 +1,95 @@
+use crate::sorting_algorithms::{Complexity, SortInfo, SortRecorder, Sorter};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+// Shuffles tried per ordering of the values by default, enough to sort with about 98% odds
+const SHUFFLES_PER_ORDER: usize = 4;
+// Longest input the default cap grows with, as 7 values already have 5040 orderings
+const MAX_HOPEFUL_LEN: usize = 6;
+// Fewest shuffles tried by default
+const MIN_SHUFFLES: usize = 100;
+
+/// Bogosort shuffles the values until they happen to come out in order. It gives up after
+/// `max_shuffles` shuffles, leaving the values unsorted and recording `SortEvent::GaveUp`.
+/// By default the cap covers every ordering a few times over for up to six values, and stays
+/// at 100 shuffles for longer inputs, which it has no real chance of sorting.
+pub struct BogoSort {
+    pub max_shuffles: Option<usize>, // Cap on the shuffles, picked from the length when None
+    rng: StdRng,                     // Drives the shuffles
+}
+
+impl Default for BogoSort {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl Sorter for BogoSort {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "bogosort",
+            title: "Bogosort Algorithm",
+            stable: false,
+            in_place: true,
+            complexity: Complexity {
+                best: "O(n)",
+                average: "O(n * n!)",
+                worst: "unbounded",
+                space: "O(1)",
+            },
+        }
+    }
+
+    fn reseed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    fn sort(&mut self, array: &mut SortRecorder) {
+        let len = array.len();
+        let max_shuffles = self
+            .max_shuffles
+            .unwrap_or_else(|| Self::default_shuffles(len));
+        for _ in 0..max_shuffles {
+            array.phase("checking");
+            if Self::is_sorted(array) {
+                array.sorted(0, len);
+                return;
+            }
+            // Fisher-Yates shuffle
+            array.phase("shuffling");
+            for i in (1..len).rev() {
+                let j = self.rng.gen_range(0..=i);
+                if i != j {
+                    array.swap(i, j);
+                }
+            }
+        }
+        array.phase("checking");
+        if Self::is_sorted(array) {
+            array.sorted(0, len);
+        } else {
+            array.phase("gave up");
+            array.give_up(max_shuffles);
+        }
+    }
+}
+
+impl BogoSort {
+    pub fn new(max_shuffles: Option<usize>) -> Self {
+        BogoSort {
+            max_shuffles,
+            rng: StdRng::seed_from_u64(0),
+        }
+    }
+    /// Cap used for `len` values when none is set: a few times the number of orderings of up
+    /// to six values, and at least `MIN_SHUFFLES`.
+    pub fn default_shuffles(len: usize) -> usize {
+        if len > MAX_HOPEFUL_LEN {
+            return MIN_SHUFFLES;
+        }
+        let orderings: usize = (1..=len).product();
+        (SHUFFLES_PER_ORDER * orderings).max(MIN_SHUFFLES)
+    }
+    fn is_sorted(array: &mut SortRecorder) -> bool {
+        (1..array.len()).all(|i| !array.less(i, i - 1))
+    }
+}
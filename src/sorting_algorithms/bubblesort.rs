@@ -0,0 +1,50 @@
+use crate::sorting_algorithms::{Complexity, SortInfo, SortRecorder, Sorter};
+use std::cmp::Ordering;
+
+#[derive(Default)]
+pub struct BubbleSort;
+
+impl Sorter for BubbleSort {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "bubblesort",
+            title: "Bubble Sort Algorithm",
+            stable: true,
+            in_place: true,
+            complexity: Complexity {
+                best: "O(n)",
+                average: "O(n^2)",
+                worst: "O(n^2)",
+                space: "O(1)",
+            },
+        }
+    }
+
+    fn sort(&mut self, array: &mut SortRecorder) {
+        let len = array.len();
+        for end in (1..len).rev() {
+            // Carry the largest value of the unsorted part up to `end`
+            let mut swapped = false;
+            array.current(0);
+            for j in 0..end {
+                if array.compare(j, j + 1) == Ordering::Greater {
+                    array.swap(j, j + 1);
+                    swapped = true;
+                } else {
+                    array.current(j + 1);
+                }
+            }
+            array.sorted(end, len);
+            if !swapped {
+                break; // No swaps means the rest is already in order
+            }
+        }
+        array.sorted(0, len);
+    }
+}
+
+impl BubbleSort {
+    pub fn new() -> Self {
+        BubbleSort
+    }
+}
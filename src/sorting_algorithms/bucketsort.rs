@@ -0,0 +1,48 @@
+use crate::sorting_algorithms::{
+    Complexity, CountingSort, InsertionSort, SortInfo, SortRecorder, Sorter,
+};
+
+#[derive(Default)]
+pub struct BucketSort;
+
+impl Sorter for BucketSort {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "bucketsort",
+            title: "Bucket Sort Algorithm",
+            stable: true,
+            in_place: false,
+            complexity: Complexity {
+                best: "O(n + k)",
+                average: "O(n + k)",
+                worst: "O(n^2)",
+                space: "O(n + k)",
+            },
+        }
+    }
+
+    fn sort(&mut self, array: &mut SortRecorder) {
+        let Some((min, max)) = CountingSort::key_range(array, 0, array.len()) else {
+            return;
+        };
+        // About sqrt(n) buckets, each covering an equal slice of the key range
+        let len = array.len();
+        let buckets = (len as f64).sqrt().ceil() as usize;
+        let span = max - min + 1;
+        let bounds = CountingSort::counting_pass(array, 0, len, buckets, |key| {
+            ((key - min) * buckets as i64 / span) as usize
+        });
+        // Sort the values of each bucket, which now sit together
+        for bucket in bounds.windows(2) {
+            array.focus(bucket[0], bucket[1]);
+            InsertionSort::sort_range(array, bucket[0], bucket[1]);
+        }
+        array.sorted(0, len);
+    }
+}
+
+impl BucketSort {
+    pub fn new() -> Self {
+        BucketSort
+    }
+}
@@ -0,0 +1,69 @@
+use crate::sorting_algorithms::{Complexity, SortInfo, SortRecorder, Sorter};
+use std::cmp::Ordering;
+
+/// Bubble sort that alternates direction, carrying the largest value right and then the
+/// smallest value left on every round trip.
+#[derive(Default)]
+pub struct CocktailShakerSort;
+
+impl Sorter for CocktailShakerSort {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "cocktailsort",
+            title: "Cocktail Shaker Sort Algorithm",
+            stable: true,
+            in_place: true,
+            complexity: Complexity {
+                best: "O(n)",
+                average: "O(n^2)",
+                worst: "O(n^2)",
+                space: "O(1)",
+            },
+        }
+    }
+
+    fn sort(&mut self, array: &mut SortRecorder) {
+        let len = array.len();
+        let (mut start, mut end) = (0, len);
+        while end - start > 1 {
+            let mut swapped = false;
+            array.current(start);
+            for j in start..end - 1 {
+                if array.compare(j, j + 1) == Ordering::Greater {
+                    array.swap(j, j + 1);
+                    swapped = true;
+                } else {
+                    array.current(j + 1);
+                }
+            }
+            end -= 1;
+            array.sorted(end, len);
+            if !swapped {
+                break;
+            }
+
+            swapped = false;
+            array.current(end - 1);
+            for j in (start..end - 1).rev() {
+                if array.compare(j, j + 1) == Ordering::Greater {
+                    array.swap(j, j + 1);
+                    swapped = true;
+                } else {
+                    array.current(j);
+                }
+            }
+            start += 1;
+            array.sorted(0, start);
+            if !swapped {
+                break;
+            }
+        }
+        array.sorted(0, len);
+    }
+}
+
+impl CocktailShakerSort {
+    pub fn new() -> Self {
+        CocktailShakerSort
+    }
+}
@@ -0,0 +1,51 @@
+use crate::sorting_algorithms::{Complexity, SortInfo, SortRecorder, Sorter};
+use std::cmp::Ordering;
+
+// Factor the gap shrinks by after every pass, found by Lacey and Box to work best
+const SHRINK: f64 = 1.3;
+
+/// Comb sort is bubble sort over a gap that shrinks each pass, so small values far to the
+/// right move left in a few long jumps instead of one position per pass.
+#[derive(Default)]
+pub struct CombSort;
+
+impl Sorter for CombSort {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "combsort",
+            title: "Comb Sort Algorithm",
+            stable: false,
+            in_place: true,
+            complexity: Complexity {
+                best: "O(n log n)",
+                average: "O(n^2 / 2^p)",
+                worst: "O(n^2)",
+                space: "O(1)",
+            },
+        }
+    }
+
+    fn sort(&mut self, array: &mut SortRecorder) {
+        let len = array.len();
+        let mut gap = len;
+        let mut swapped = true;
+        while gap > 1 || swapped {
+            gap = ((gap as f64 / SHRINK) as usize).max(1);
+            array.gap(gap);
+            swapped = false;
+            for i in 0..len.saturating_sub(gap) {
+                if array.compare(i, i + gap) == Ordering::Greater {
+                    array.swap(i, i + gap);
+                    swapped = true;
+                }
+            }
+        }
+        array.sorted(0, len);
+    }
+}
+
+impl CombSort {
+    pub fn new() -> Self {
+        CombSort
+    }
+}
@@ -0,0 +1,108 @@
+use crate::sorting_algorithms::{Complexity, LsdRadixSort, SortInfo, SortRecorder, Sorter};
+
+// Counts allowed per value sorted, and at least this many for short inputs. Wider key ranges
+// would spend most of the time and memory on counts that stay empty.
+const COUNTS_PER_VALUE: usize = 8;
+const MIN_COUNT_LIMIT: usize = 1024;
+
+/// Counting sort, with one count per key between the smallest and the largest. Keys spread
+/// over more than a few counts per value are sorted by LSD radix sort in `base` instead.
+pub struct CountingSort {
+    pub base: u32, // Digit base of the radix sort fallback
+}
+
+impl Default for CountingSort {
+    fn default() -> Self {
+        Self::new(10)
+    }
+}
+
+impl Sorter for CountingSort {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "countingsort",
+            title: "Counting Sort Algorithm",
+            stable: true,
+            in_place: false,
+            complexity: Complexity {
+                best: "O(n + k)",
+                average: "O(n + k)",
+                worst: "O(n + k)",
+                space: "O(n + k)",
+            },
+        }
+    }
+
+    fn sort(&mut self, array: &mut SortRecorder) {
+        let Some((min, max)) = Self::key_range(array, 0, array.len()) else {
+            return;
+        };
+        // One count for every key between the smallest and largest, in i64 as the span of
+        // two i32 keys can be wider than an i32
+        let len = array.len();
+        let counts = (max - min) as u64 + 1;
+        let limit = len.saturating_mul(COUNTS_PER_VALUE).max(MIN_COUNT_LIMIT);
+        if counts > limit as u64 {
+            // Radix sort counts one digit at a time, so it needs only as many counts as its base
+            array.phase("keys too far apart, falling back to LSD radix sort");
+            LsdRadixSort::new(self.base).sort(array);
+            return;
+        }
+        Self::counting_pass(array, 0, len, counts as usize, |key| (key - min) as usize);
+        array.sorted(0, len);
+    }
+}
+
+impl CountingSort {
+    pub fn new(base: u32) -> Self {
+        CountingSort { base: base.max(2) }
+    }
+    /// Returns the smallest and largest key in `start..end`, read without recording.
+    pub fn key_range(array: &SortRecorder, start: usize, end: usize) -> Option<(i64, i64)> {
+        let keys = (start..end).map(|idx| array.key(idx) as i64);
+        Some((keys.clone().min()?, keys.max()?))
+    }
+    /// Stable counting sort of `start..end` into `buckets` buckets picked by `bucket_of`,
+    /// which is given each key. Returns where every bucket starts in the sorted range,
+    /// followed by `end`.
+    pub fn counting_pass(
+        array: &mut SortRecorder,
+        start: usize,
+        end: usize,
+        buckets: usize,
+        bucket_of: impl Fn(i64) -> usize,
+    ) -> Vec<usize> {
+        array.buckets(buckets);
+        array.alloc(buckets);
+        let mut counts = vec![0; buckets];
+        for idx in start..end {
+            let bucket = bucket_of(array.key(idx) as i64);
+            array.deposit(idx, bucket);
+            counts[bucket] += 1;
+        }
+
+        // Prefix sums give the end of every bucket in the output
+        let mut bounds = Vec::with_capacity(buckets + 1);
+        let mut next = start;
+        for count in counts.iter() {
+            bounds.push(next);
+            next += count;
+        }
+        bounds.push(end);
+        let mut ends = bounds[1..].to_vec();
+
+        // Place the values from the back so equal keys keep their order
+        let src = array.values()[start..end].to_vec();
+        array.alloc(src.len());
+        for &value in src.iter().rev() {
+            let bucket = bucket_of(array.key_of(value) as i64);
+            ends[bucket] -= 1;
+            array.withdraw(bucket);
+            array.write(ends[bucket], value);
+        }
+        array.free(src.len());
+        array.free(buckets);
+        array.buckets(0);
+        bounds
+    }
+}
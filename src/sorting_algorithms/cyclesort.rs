@@ -0,0 +1,76 @@
+use crate::sorting_algorithms::{Complexity, SortInfo, SortRecorder, Sorter};
+use std::cmp::Ordering;
+
+/// Cycle sort writes every value straight to its final position, found by counting the
+/// smaller values, and moves the value it displaces on around the cycle. No value is written
+/// more than once, the fewest writes any sort can manage.
+#[derive(Default)]
+pub struct CycleSort;
+
+impl Sorter for CycleSort {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "cyclesort",
+            title: "Cycle Sort Algorithm",
+            stable: false,
+            in_place: true,
+            complexity: Complexity {
+                best: "O(n^2)",
+                average: "O(n^2)",
+                worst: "O(n^2)",
+                space: "O(1)",
+            },
+        }
+    }
+
+    fn sort(&mut self, array: &mut SortRecorder) {
+        let len = array.len();
+        // The value being carried around a cycle is held outside the array
+        array.alloc(1);
+        for cycle_start in 0..len.saturating_sub(1) {
+            array.sorted(0, cycle_start);
+            let mut item = array[cycle_start];
+            let mut pos = Self::position(array, cycle_start, item);
+            if pos == cycle_start {
+                continue; // Already in place
+            }
+            loop {
+                // Equal values go after the ones already placed
+                while array.compare_values(pos, array[pos], cycle_start, item) == Ordering::Equal {
+                    pos += 1;
+                }
+                array.current(pos);
+                let displaced = array[pos];
+                array.write(pos, item);
+                item = displaced;
+                if pos == cycle_start {
+                    break;
+                }
+                pos = Self::position(array, cycle_start, item);
+                if pos == cycle_start {
+                    array.current(pos);
+                    array.write(pos, item);
+                    break;
+                }
+            }
+        }
+        array.free(1);
+        array.sorted(0, len);
+    }
+}
+
+impl CycleSort {
+    pub fn new() -> Self {
+        CycleSort
+    }
+    // Final position of `item` among the values from `cycle_start` on
+    fn position(array: &mut SortRecorder, cycle_start: usize, item: i32) -> usize {
+        let mut pos = cycle_start;
+        for i in cycle_start + 1..array.len() {
+            if array.compare_values(i, array[i], cycle_start, item) == Ordering::Less {
+                pos += 1;
+            }
+        }
+        pos
+    }
+}
@@ -0,0 +1,127 @@
+use rand::{seq::SliceRandom, Rng};
+
+/// Shape of the input a `SortGraph` is filled with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Distribution {
+    Random,       // Uniformly random values
+    Sorted,       // Random values in ascending order
+    Reversed,     // Random values in descending order
+    NearlySorted, // Sorted, with a few nearby pairs swapped
+    FewUnique,    // Random picks from four evenly spaced values
+    OrganPipe,    // Rises to the middle and falls back down
+    Sawtooth,     // Four ascending ramps one after another
+    AllEqual,     // Every value the same
+    Gaussian,     // Normally distributed around the middle of the range
+}
+
+impl Distribution {
+    pub const ALL: [Distribution; 9] = [
+        Self::Random,
+        Self::Sorted,
+        Self::Reversed,
+        Self::NearlySorted,
+        Self::FewUnique,
+        Self::OrganPipe,
+        Self::Sawtooth,
+        Self::AllEqual,
+        Self::Gaussian,
+    ];
+
+    /// Registry key used to pick the distribution at runtime.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Random => "random",
+            Self::Sorted => "sorted",
+            Self::Reversed => "reversed",
+            Self::NearlySorted => "nearly-sorted",
+            Self::FewUnique => "few-unique",
+            Self::OrganPipe => "organ-pipe",
+            Self::Sawtooth => "sawtooth",
+            Self::AllEqual => "all-equal",
+            Self::Gaussian => "gaussian",
+        }
+    }
+
+    /// Generates `len` values between `min` and `max` inclusive.
+    pub fn generate<R: Rng>(&self, rng: &mut R, len: usize, min: i32, max: i32) -> Vec<i32> {
+        // Maps `step` out of `steps` onto the range, so shapes always span min to max
+        let lerp = |step: usize, steps: usize| {
+            if steps == 0 {
+                return min;
+            }
+            let span = max as i64 - min as i64;
+            (min as i64 + span * step as i64 / steps as i64) as i32
+        };
+        let uniform =
+            |rng: &mut R| -> Vec<i32> { (0..len).map(|_| rng.gen_range(min..=max)).collect() };
+
+        match self {
+            Self::Random => uniform(rng),
+            Self::Sorted => {
+                let mut values = uniform(rng);
+                values.sort_unstable();
+                values
+            }
+            Self::Reversed => {
+                let mut values = uniform(rng);
+                values.sort_unstable_by(|a, b| b.cmp(a));
+                values
+            }
+            Self::NearlySorted => {
+                let mut values = uniform(rng);
+                values.sort_unstable();
+                if len > 1 {
+                    for _ in 0..(len / 10).max(1) {
+                        let i = rng.gen_range(0..len - 1);
+                        let j = (i + rng.gen_range(1..=3)).min(len - 1);
+                        values.swap(i, j);
+                    }
+                }
+                values
+            }
+            Self::FewUnique => {
+                let levels: Vec<i32> = (0..4).map(|level| lerp(level, 3)).collect();
+                (0..len).map(|_| *levels.choose(rng).unwrap()).collect()
+            }
+            Self::OrganPipe => {
+                let half = len.saturating_sub(1) / 2;
+                (0..len).map(|i| lerp(i.min(len - 1 - i), half)).collect()
+            }
+            Self::Sawtooth => {
+                let tooth = len.div_ceil(4).max(1);
+                (0..len).map(|i| lerp(i % tooth, tooth - 1)).collect()
+            }
+            Self::AllEqual => vec![lerp(1, 2); len],
+            Self::Gaussian => {
+                let mean = (min as f64 + max as f64) / 2.0;
+                let deviation = (max as f64 - min as f64) / 6.0;
+                (0..len)
+                    .map(|_| {
+                        // Box-Muller transform of two uniform samples
+                        let u1: f64 = 1.0 - rng.gen::<f64>();
+                        let u2: f64 = rng.gen();
+                        let normal = (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos();
+                        (mean + normal * deviation)
+                            .round()
+                            .clamp(min as f64, max as f64) as i32
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Looks up a distribution by its registry name, ignoring case.
+pub fn find_distribution(name: &str) -> Option<Distribution> {
+    Distribution::ALL
+        .into_iter()
+        .find(|distribution| distribution.name().eq_ignore_ascii_case(name.trim()))
+}
+
+/// Returns the registry names of every available distribution.
+pub fn distribution_names() -> Vec<&'static str> {
+    Distribution::ALL
+        .iter()
+        .map(|distribution| distribution.name())
+        .collect()
+}
@@ -0,0 +1,50 @@
+use crate::sorting_algorithms::{BinarySearch, Complexity, SearchInfo, Searcher, SortRecorder};
+use std::cmp::Ordering;
+
+/// Exponential search. Probes at doubling distances from the start until one passes the
+/// target, then binary searches the last stretch it skipped. Targets near the start are found
+/// in time logarithmic in their position rather than in the length.
+#[derive(Default)]
+pub struct ExponentialSearch;
+
+impl Searcher for ExponentialSearch {
+    fn info(&self) -> SearchInfo {
+        SearchInfo {
+            name: "exponentialsearch",
+            title: "Exponential Search Algorithm",
+            complexity: Complexity {
+                best: "O(1)",
+                average: "O(log i)",
+                worst: "O(log n)",
+                space: "O(1)",
+            },
+        }
+    }
+
+    fn search(&mut self, array: &mut SortRecorder, target: i32) -> Option<usize> {
+        let len = array.len();
+        let (mut low, mut high) = (0, len);
+        let mut bound = 1;
+        array.phase("galloping");
+        while bound <= len {
+            array.focus(low, len);
+            match array.probe(bound - 1, target) {
+                Ordering::Less => low = bound,
+                Ordering::Equal => return Some(bound - 1),
+                Ordering::Greater => {
+                    high = bound - 1;
+                    break;
+                }
+            }
+            bound *= 2;
+        }
+        array.phase("binary search");
+        BinarySearch::search_range(array, target, low, high)
+    }
+}
+
+impl ExponentialSearch {
+    pub fn new() -> Self {
+        ExponentialSearch
+    }
+}
@@ -0,0 +1,44 @@
+use crate::sorting_algorithms::{Complexity, SortInfo, SortRecorder, Sorter};
+
+/// Gnome sort walks forward while neighbors are in order and steps back after swapping a pair
+/// that is not, like a garden gnome sorting flower pots.
+#[derive(Default)]
+pub struct GnomeSort;
+
+impl Sorter for GnomeSort {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "gnomesort",
+            title: "Gnome Sort Algorithm",
+            stable: true,
+            in_place: true,
+            complexity: Complexity {
+                best: "O(n)",
+                average: "O(n^2)",
+                worst: "O(n^2)",
+                space: "O(1)",
+            },
+        }
+    }
+
+    fn sort(&mut self, array: &mut SortRecorder) {
+        let len = array.len();
+        let mut pos = 0;
+        while pos < len {
+            array.current(pos);
+            if pos == 0 || !array.less(pos, pos - 1) {
+                pos += 1;
+            } else {
+                array.swap(pos, pos - 1);
+                pos -= 1;
+            }
+        }
+        array.sorted(0, len);
+    }
+}
+
+impl GnomeSort {
+    pub fn new() -> Self {
+        GnomeSort
+    }
+}
@@ -0,0 +1,314 @@
+use crate::sorting_algorithms::SortEvent;
+use colored::{Color, Colorize};
+
+/// How the nodes of a heap are stored in the array, which decides where each node's children
+/// are and so how `HeapTree` draws it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeapShape {
+    Ary(usize), // Children of node k at d*k+1..=d*k+d, level by level; 2 is the binary heap
+    Weak,       // Weak heap: the root has one child, node k has 2k and 2k+1 unless it is flipped
+    Leonardo,   // Smoothsort's forest of Leonardo trees, each root at the right end of its tree
+}
+
+impl Default for HeapShape {
+    fn default() -> Self {
+        Self::BINARY
+    }
+}
+
+impl HeapShape {
+    pub const BINARY: HeapShape = HeapShape::Ary(2);
+
+    /// Roots of the heap for `len` nodes, as (node, order) pairs from left to right. The order
+    /// only matters for Leonardo trees, where it gives the tree's size.
+    pub fn roots(&self, len: usize) -> Vec<(usize, usize)> {
+        match self {
+            _ if len == 0 => Vec::new(),
+            Self::Ary(_) | Self::Weak => vec![(0, 0)],
+            Self::Leonardo => {
+                let mut start = 0;
+                leonardo_orders(len)
+                    .into_iter()
+                    .map(|order| {
+                        start += leonardo(order);
+                        (start - 1, order)
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Children of `node` with `order`, from left to right, among the first `len` nodes.
+    /// `flipped` holds the reverse bit of each node of a weak heap.
+    pub fn children(
+        &self,
+        (node, order): (usize, usize),
+        len: usize,
+        flipped: &[bool],
+    ) -> Vec<(usize, usize)> {
+        let children = match *self {
+            Self::Ary(arity) => (1..=arity).map(|i| (arity * node + i, 0)).collect(),
+            Self::Weak if node == 0 => vec![(1, 0)],
+            Self::Weak => {
+                let reversed = flipped.get(node).copied().unwrap_or(false) as usize;
+                vec![(2 * node + reversed, 0), (2 * node + 1 - reversed, 0)]
+            }
+            Self::Leonardo if order < 2 => Vec::new(),
+            Self::Leonardo => vec![
+                (node - 1 - leonardo(order - 2), order - 1),
+                (node - 1, order - 2),
+            ],
+        };
+        children
+            .into_iter()
+            .filter(|&(child, _)| child < len)
+            .collect()
+    }
+}
+
+/// Size of a Leonardo tree of `order`: 1, 1, 3, 5, 9, 15, ...
+pub fn leonardo(order: usize) -> usize {
+    let (mut size, mut next) = (1, 1);
+    for _ in 0..order {
+        (size, next) = (next, size + next + 1);
+    }
+    size
+}
+
+/// Orders of the Leonardo trees smoothsort splits `len` values into, from left to right.
+/// Each value joins the two trees before it when their orders are neighbors, and starts a
+/// new tree otherwise.
+pub fn leonardo_orders(len: usize) -> Vec<usize> {
+    let mut orders: Vec<usize> = Vec::new();
+    for _ in 0..len {
+        match orders[..] {
+            [.., left, right] if left == right + 1 => {
+                orders.truncate(orders.len() - 2);
+                orders.push(left + 1);
+            }
+            [.., 1] => orders.push(0),
+            _ => orders.push(1),
+        }
+    }
+    orders
+}
+
+/// Draws a heap stored in an array as a tree of text lines, shown beside the bars. Levels
+/// that do not fit the columns or rows it is given are left out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeapTree {
+    pub shape: HeapShape,
+    pub cell: usize,    // Characters per node on the bottom level, including a space
+    pub levels: usize,  // Most levels drawn
+    pub columns: usize, // Width of every line
+    len: usize,         // Largest heap drawn, which fixes where the nodes of array heaps go
+}
+
+// One character of the drawing and its color
+type Cell = (char, Option<Color>);
+
+// A node placed in the drawing
+struct Placed {
+    node: usize,
+    level: usize,
+    center: usize,         // Column at the middle of the node
+    parent: Option<usize>, // Position of the parent among the placed nodes
+}
+
+impl HeapTree {
+    /// Sizes the tree for heaps of `shape` of up to `heap_len` values between `low` and
+    /// `high`, at most `max_columns` wide.
+    pub fn new(shape: HeapShape, heap_len: usize, low: i32, high: i32, max_columns: usize) -> Self {
+        let cell = low.to_string().len().max(high.to_string().len()) + 1;
+        let mut tree = Self {
+            shape,
+            cell,
+            levels: 0,
+            columns: 0,
+            len: heap_len,
+        };
+        // Leonardo forests change shape as they grow, so every size up to the largest has to fit
+        let lens = match shape {
+            HeapShape::Leonardo => 1..=heap_len,
+            _ => heap_len..=heap_len,
+        };
+        let deepest = Self {
+            levels: usize::MAX,
+            ..tree
+        };
+        let depth = lens
+            .clone()
+            .map(|len| deepest.depth(len))
+            .max()
+            .unwrap_or(0);
+        for levels in 1..=depth {
+            let wider = Self { levels, ..tree };
+            let columns = lens
+                .clone()
+                .map(|len| wider.layout(len, &[]).1)
+                .max()
+                .unwrap_or(0);
+            if columns > max_columns && levels > 1 {
+                break;
+            }
+            tree = Self { columns, ..wider };
+        }
+        tree
+    }
+
+    /// Returns `rows` lines drawing the heap at `start..end` of `values`, with the sorted tail
+    /// `end..sorted_end` detached on the last line. `flipped` holds the reverse bits of a weak
+    /// heap by array index. Values compared by `event` are yellow, and a swap is drawn in red
+    /// and green, along the edge it moves a value over when it swaps a parent and child.
+    pub fn lines(
+        &self,
+        values: &[i32],
+        (start, end, sorted_end): (usize, usize, usize),
+        flipped: &[bool],
+        event: &SortEvent,
+        rows: usize,
+    ) -> Vec<String> {
+        let mut grid: Vec<Vec<Cell>> = vec![vec![(' ', None); self.columns]; rows];
+        let end = end.min(values.len());
+        let heap_len = end.saturating_sub(start);
+        // Each level takes a row of nodes and a row of edges, leaving a blank and the tail
+        let levels = self.levels.min(rows.saturating_sub(1) / 2);
+        let tree = Self { levels, ..*self };
+        // Array heaps are laid out for the largest heap, so nodes stay put as the heap shrinks
+        let layout_len = match self.shape {
+            HeapShape::Leonardo => heap_len,
+            _ => heap_len.max(self.len),
+        };
+        let (placed, _) = tree.layout(layout_len, flipped.get(start..).unwrap_or(&[]));
+
+        let node_color = |idx: usize| match *event {
+            SortEvent::Compare(i, j) if idx == i || idx == j => Some(Color::BrightYellow),
+            SortEvent::Swap(i, _) if idx == i => Some(Color::BrightRed),
+            SortEvent::Swap(_, j) if idx == j => Some(Color::Green),
+            _ => None,
+        };
+        let swapped_edge = |parent: usize, child: usize| {
+            matches!(*event, SortEvent::Swap(i, j)
+                if (i, j) == (parent, child) || (j, i) == (parent, child))
+        };
+
+        for place in placed.iter().filter(|place| place.node < heap_len) {
+            let text = values[start + place.node].to_string();
+            let left = place.center.saturating_sub(text.len() / 2);
+            let color = node_color(start + place.node);
+            for (offset, ch) in text.chars().enumerate() {
+                if let Some(cell) = grid[2 * place.level].get_mut(left + offset) {
+                    *cell = (ch, color);
+                }
+            }
+            if let Some(parent) = place.parent.map(|parent| &placed[parent]) {
+                let x = (place.center + parent.center) / 2;
+                let ch = match place.center.cmp(&parent.center) {
+                    std::cmp::Ordering::Less => '/',
+                    std::cmp::Ordering::Equal => '|',
+                    std::cmp::Ordering::Greater => '\\',
+                };
+                let color = match swapped_edge(start + parent.node, start + place.node) {
+                    true => Color::BrightRed,
+                    false => Color::BrightBlack,
+                };
+                if let Some(cell) = grid[2 * place.level - 1].get_mut(x) {
+                    *cell = (ch, Some(color));
+                }
+            }
+        }
+
+        // The sorted tail, which no longer belongs to the heap
+        if rows > 0 && end < sorted_end {
+            let tail: Vec<String> = values[end..sorted_end.min(values.len())]
+                .iter()
+                .map(|value| value.to_string())
+                .collect();
+            let mut text = format!("sorted {}", tail.join(" "));
+            if text.chars().count() > self.columns {
+                text = text.chars().take(self.columns.saturating_sub(1)).collect();
+                text.push('…');
+            }
+            for (x, ch) in text.chars().enumerate() {
+                grid[rows - 1][x] = (ch, Some(Color::Green));
+            }
+        }
+
+        grid.iter()
+            .map(|row| {
+                row.iter()
+                    .map(|&(ch, color)| match color {
+                        Some(color) => ch.to_string().color(color).to_string(),
+                        None => ch.to_string(),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    // Levels drawn for a heap of `len` values, at most `self.levels`
+    fn depth(&self, len: usize) -> usize {
+        let (placed, _) = self.layout(len, &[]);
+        placed
+            .iter()
+            .map(|place| place.level + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    // Places the nodes of a heap of `len` values down to `self.levels`, each bottom node in a
+    // cell of its own and each parent centered over its children. Returns the nodes and the
+    // width they take.
+    fn layout(&self, len: usize, flipped: &[bool]) -> (Vec<Placed>, usize) {
+        let mut placed = Vec::new();
+        let mut width = 0;
+        if self.levels > 0 {
+            for root in self.shape.roots(len) {
+                width += self.place(root, 0, None, width, len, flipped, &mut placed);
+            }
+        }
+        (placed, width)
+    }
+
+    // Places the subtree under `node` from column `left`, returning its width
+    #[allow(clippy::too_many_arguments)]
+    fn place(
+        &self,
+        node: (usize, usize),
+        level: usize,
+        parent: Option<usize>,
+        left: usize,
+        len: usize,
+        flipped: &[bool],
+        placed: &mut Vec<Placed>,
+    ) -> usize {
+        let position = placed.len();
+        placed.push(Placed {
+            node: node.0,
+            level,
+            center: left + self.cell / 2,
+            parent,
+        });
+        if level + 1 >= self.levels {
+            return self.cell;
+        }
+        let mut width = 0;
+        let mut centers = Vec::new();
+        for child in self.shape.children(node, len, flipped) {
+            centers.push(placed.len());
+            width += self.place(
+                child,
+                level + 1,
+                Some(position),
+                left + width,
+                len,
+                flipped,
+                placed,
+            );
+        }
+        if let (Some(&first), Some(&last)) = (centers.first(), centers.last()) {
+            placed[position].center = (placed[first].center + placed[last].center) / 2;
+        }
+        width.max(self.cell)
+    }
+}
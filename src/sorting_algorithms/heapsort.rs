@@ -1,14 +1,73 @@
-use crate::sorting_algorithms::SortGraph;
-use std::fmt;
+use crate::sorting_algorithms::{
+    Complexity, HeapShape, SortGraph, SortInfo, SortRecorder, SortTrace, Sorter,
+};
+use std::{cmp::Ordering, error::Error, fmt};
 
-pub struct Heap<'a, 'b> {
-    pub graph: &'a mut SortGraph<'b, 'b>,
-    pub nodes: Vec<i32>,
+/// Which value a `Heap` keeps at its root.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HeapKind {
+    #[default]
+    Max, // Largest value on top, every parent at least as large as its children
+    Min, // Smallest value on top, every parent at most as large as its children
 }
 
-impl fmt::Display for Heap<'_, '_> {
+impl HeapKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Max => "max-heap",
+            Self::Min => "min-heap",
+        }
+    }
+}
+
+/// Reasons a priority queue operation on a `Heap` was refused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeapError {
+    OutOfRange {
+        index: usize,
+        len: usize,
+    },
+    KeyNotIncreased {
+        index: usize,
+        current: i32,
+        key: i32,
+    }, // New key ranks below the old one
+}
+
+impl fmt::Display for HeapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfRange { index, len } => {
+                write!(
+                    f,
+                    "index {} is out of range for a heap of {} values",
+                    index, len
+                )
+            }
+            Self::KeyNotIncreased {
+                index,
+                current,
+                key,
+            } => write!(
+                f,
+                "key {} at index {} cannot be increased to {}, it would move away from the root",
+                current, index, key
+            ),
+        }
+    }
+}
+
+impl Error for HeapError {}
+
+#[derive(Default)]
+pub struct Heap {
+    pub nodes: SortRecorder,
+    pub kind: HeapKind,
+}
+
+impl fmt::Display for Heap {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for (idx, &val) in self.nodes.iter().enumerate() {
+        for (idx, &val) in self.nodes.values().iter().enumerate() {
             let left_idx = 2 * idx + 1;
             let right_idx = 2 * idx + 2;
 
@@ -32,108 +91,302 @@ impl fmt::Display for Heap<'_, '_> {
     }
 }
 
-impl<'a, 'b> Heap<'a, 'b> {
-    pub fn new(graph: &'a mut SortGraph<'b, 'b>) -> Self {
+impl Sorter for Heap {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "heapsort",
+            title: "HeapSort Algorithm",
+            stable: false,
+            in_place: true,
+            complexity: Complexity {
+                best: "O(n log n)",
+                average: "O(n log n)",
+                worst: "O(n log n)",
+                space: "O(1)",
+            },
+        }
+    }
+
+    fn sort(&mut self, array: &mut SortRecorder) {
+        Self::heapsort(array);
+    }
+}
+
+/// Heapsort on a heap whose nodes have `arity` children instead of two. Wider heaps are
+/// shallower, so a value sifts down fewer levels but compares more children on each.
+pub struct DaryHeapSort {
+    pub arity: usize,
+}
+
+impl Default for DaryHeapSort {
+    fn default() -> Self {
+        Self::new(3)
+    }
+}
+
+impl Sorter for DaryHeapSort {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "daryheapsort",
+            title: "D-ary Heap Sort Algorithm",
+            stable: false,
+            in_place: true,
+            complexity: Complexity {
+                best: "O(n log n)",
+                average: "O(n log n)",
+                worst: "O(n log n)",
+                space: "O(1)",
+            },
+        }
+    }
+
+    fn title(&self) -> String {
+        format!("{} ({} children per node)", self.info().title, self.arity)
+    }
+
+    fn sort(&mut self, array: &mut SortRecorder) {
+        let len = array.len();
+        Heap::heapsort_range_ary(array, self.arity, 0, len);
+    }
+}
+
+impl DaryHeapSort {
+    pub fn new(arity: usize) -> Self {
+        DaryHeapSort {
+            arity: arity.max(2),
+        }
+    }
+}
+
+impl Heap {
+    pub fn new() -> Self {
+        Self::with_kind(HeapKind::Max)
+    }
+    pub fn with_kind(kind: HeapKind) -> Self {
         Self {
-            graph,
-            nodes: Vec::new(),
+            nodes: SortRecorder::default(),
+            kind,
         }
     }
 
-    pub fn from_graph(graph: &'a mut SortGraph<'b, 'b>) -> Self {
-        let nodes = graph.values.clone();
-        Self { graph, nodes }
+    pub fn from_graph(graph: &SortGraph<'_, '_>) -> Self {
+        let nodes = SortRecorder::new(graph.values.clone());
+        Self {
+            nodes,
+            kind: HeapKind::Max,
+        }
     }
     pub fn insert(&mut self, value: i32) {
         self.nodes.push(value);
-        let mut idx = self.nodes.len() - 1;
-        while idx > 0 {
-            let parent = (idx - 1) / 2;
-            if self.nodes[parent] < self.nodes[idx] {
-                self.nodes.swap(parent, idx);
-                idx = parent;
-            } else {
-                break;
-            }
-        }
+        let idx = self.nodes.len() - 1;
+        self.show_tree();
+        self.nodes.current(idx);
+        Self::sift_up(&mut self.nodes, self.kind, idx);
     }
-    pub fn update_graph(&mut self) {
-        self.graph.values = self.nodes.clone();
+    pub fn update_graph(&self, graph: &mut SortGraph<'_, '_>) {
+        graph.values = self.nodes.values().to_vec();
     }
-    pub fn update_from_slice(&mut self, vals: &[i32]) {
+    pub fn update_from_slice(&mut self, graph: &mut SortGraph<'_, '_>, vals: &[i32]) {
         for val in vals {
             self.insert(*val);
         }
-        self.update_graph();
+        self.update_graph(graph);
     }
     pub fn is_empty(&self) -> bool {
         self.nodes.is_empty()
     }
     pub fn clear(&mut self) {
-        self.nodes.clear()
+        self.nodes = SortRecorder::default();
+    }
+    /// Returns the steps recorded by the operations performed on this heap so far.
+    pub fn trace(&self) -> SortTrace {
+        self.nodes.clone().into_trace()
+    }
+    /// Returns the steps recorded since the last call, and starts recording afresh from the
+    /// current values, so each operation can be played on its own.
+    pub fn take_trace(&mut self) -> SortTrace {
+        let values = self.nodes.values().to_vec();
+        std::mem::replace(&mut self.nodes, SortRecorder::new(values)).into_trace()
     }
 
-    pub fn extract_max(&mut self) -> Option<i32> {
+    /// Removes and returns the root, the largest value of a max-heap or the smallest of a
+    /// min-heap.
+    pub fn extract_top(&mut self) -> Option<i32> {
         if self.is_empty() {
             return None;
         }
-        let max = self.nodes.swap_remove(0);
-        if !self.nodes.is_empty() {
-            self.sift_down(0, self.nodes.len() - 1);
+        self.show_tree();
+        self.nodes.current(0);
+        let last = self.nodes.len() - 1;
+        self.nodes.swap(0, last);
+        let top = self.nodes.pop();
+        self.show_tree();
+        let len = self.nodes.len();
+        Self::sift_down(&mut self.nodes, self.kind, 0, len);
+        top
+    }
+    /// Returns the root without removing it.
+    pub fn peek(&mut self) -> Option<i32> {
+        self.show_tree();
+        if self.is_empty() {
+            return None;
+        }
+        self.nodes.current(0);
+        Some(self.nodes[0])
+    }
+    /// Replaces the key at `index` with one that ranks at least as high, which is a larger
+    /// key in a max-heap and a smaller one in a min-heap, and sifts it up to its place.
+    pub fn increase_key(&mut self, index: usize, key: i32) -> Result<(), HeapError> {
+        let len = self.nodes.len();
+        if index >= len {
+            return Err(HeapError::OutOfRange { index, len });
+        }
+        let current = self.nodes[index];
+        let increased = match self.kind {
+            HeapKind::Max => key >= current,
+            HeapKind::Min => key <= current,
+        };
+        if !increased {
+            return Err(HeapError::KeyNotIncreased {
+                index,
+                current,
+                key,
+            });
+        }
+        self.show_tree();
+        self.nodes.current(index);
+        self.nodes.write(index, key);
+        Self::sift_up(&mut self.nodes, self.kind, index);
+        Ok(())
+    }
+    /// Removes and returns the value at `index`. The last value takes its place and is
+    /// sifted up or down, whichever restores the heap.
+    pub fn delete(&mut self, index: usize) -> Result<i32, HeapError> {
+        let len = self.nodes.len();
+        if index >= len {
+            return Err(HeapError::OutOfRange { index, len });
+        }
+        self.show_tree();
+        self.nodes.current(index);
+        let last = len - 1;
+        if index != last {
+            self.nodes.swap(index, last);
+        }
+        let value = self.nodes.pop().expect("index checked against the length");
+        self.show_tree();
+        if index < last {
+            self.nodes.current(index);
+            let parent = index.saturating_sub(1) / 2;
+            if index > 0 && Self::outranks(&mut self.nodes, self.kind, index, parent) {
+                Self::sift_up(&mut self.nodes, self.kind, index);
+            } else {
+                Self::sift_down(&mut self.nodes, self.kind, index, last);
+            }
+        }
+        Ok(value)
+    }
+    /// Replaces the contents with `values` and turns them into a heap bottom up, sifting down
+    /// every parent from the last one to the root.
+    pub fn build_heap(&mut self, values: &[i32]) {
+        while self.nodes.pop().is_some() {}
+        for &value in values {
+            self.nodes.push(value);
+        }
+        self.show_tree();
+        for idx in (0..values.len() / 2).rev() {
+            self.nodes.current(idx);
+            Self::sift_down(&mut self.nodes, self.kind, idx, values.len());
         }
-        Some(max)
     }
-    fn sift_down(&mut self, idx: usize, end: usize) {
+    // Draws the whole array as the heap tree
+    fn show_tree(&mut self) {
+        let len = self.nodes.len();
+        self.nodes.heap(0, len, len);
+    }
+    // Whether the value at `i` belongs above the value at `j` in a heap of `kind`
+    fn outranks(array: &mut SortRecorder, kind: HeapKind, i: usize, j: usize) -> bool {
+        let order = array.compare(i, j);
+        match kind {
+            HeapKind::Max => order == Ordering::Greater,
+            HeapKind::Min => order == Ordering::Less,
+        }
+    }
+    /// Sifts the value at `idx` down a binary heap of `kind` stored at `0..end`.
+    pub fn sift_down(array: &mut SortRecorder, kind: HeapKind, idx: usize, end: usize) {
+        Self::sift_down_ary(array, kind, 2, 0, idx, end);
+    }
+    // Sifts down within a heap of `kind` with `arity` children per node stored at
+    // `base..end`, with `idx` counted from `base`
+    fn sift_down_ary(
+        array: &mut SortRecorder,
+        kind: HeapKind,
+        arity: usize,
+        base: usize,
+        idx: usize,
+        end: usize,
+    ) {
+        let len = end - base;
         let mut idx = idx;
-        let mut child = 2 * idx + 1;
-        while child < end {
-            let right = child + 1;
-            if right < end && self.nodes[right] > self.nodes[child] {
-                child = right;
+        let mut first = arity * idx + 1;
+        while first < len {
+            // The highest ranked child, which is the only one that may move up
+            let mut child = first;
+            for next in first + 1..(first + arity).min(len) {
+                if Self::outranks(array, kind, base + next, base + child) {
+                    child = next;
+                }
             }
-            if self.nodes[child] > self.nodes[idx] {
-                self.nodes.swap(idx, child);
-                self.graph.display_simple_swap_graph(Some((idx, child)));
-                self.update_graph();
+            if Self::outranks(array, kind, base + child, base + idx) {
+                array.swap(base + idx, base + child);
                 idx = child;
-                child = 2 * idx + 1;
+                first = arity * idx + 1;
             } else {
                 break;
             }
         }
     }
-    pub fn sift_up(&mut self, mut idx: usize) {
+    /// Sifts the value at `idx` up a binary heap of `kind` stored from index 0.
+    pub fn sift_up(array: &mut SortRecorder, kind: HeapKind, mut idx: usize) {
         while idx > 0 {
             let parent_idx = (idx - 1) / 2;
-            if self.nodes[idx] > self.nodes[parent_idx] {
-                self.nodes.swap(idx, parent_idx);
-                self.graph
-                    .display_simple_swap_graph(Some((idx, parent_idx)));
-                self.update_graph();
+            if Self::outranks(array, kind, idx, parent_idx) {
+                array.swap(idx, parent_idx);
                 idx = parent_idx;
             } else {
                 break;
             }
         }
     }
-    fn build_max_heap(&mut self) {
-        let start = (self.nodes.len() / 2).saturating_sub(1);
-        for i in (0..=start).rev() {
-            self.sift_down(i, self.nodes.len());
+    fn build_max_heap(array: &mut SortRecorder, arity: usize, start: usize, end: usize) {
+        let last_parent = (end - start).saturating_sub(2) / arity;
+        for i in (0..=last_parent).rev() {
+            Self::sift_down_ary(array, HeapKind::Max, arity, start, i, end);
         }
     }
 
-    pub fn heapsort(&mut self) {
-        self.graph.display_graph();
-        self.build_max_heap();
-        for end in (1..self.nodes.len()).rev() {
-            self.nodes.swap(0, end);
-            self.graph.display_simple_swap_graph(Some((0, end)));
-            self.update_graph();
-            self.graph.display_simple_swap_graph(Some((0, end)));
-            self.sift_down(0, end);
+    pub fn heapsort(array: &mut SortRecorder) {
+        let len = array.len();
+        Self::heapsort_range(array, 0, len);
+    }
+    /// Heapsorts `start..end` on its own, for hybrid sorts that fall back to it.
+    pub fn heapsort_range(array: &mut SortRecorder, start: usize, end: usize) {
+        Self::heapsort_range_ary(array, 2, start, end);
+    }
+    // Heapsorts `start..end` with a heap of `arity` children per node
+    fn heapsort_range_ary(array: &mut SortRecorder, arity: usize, start: usize, end: usize) {
+        if end - start <= 1 {
+            return;
+        }
+        let shape = HeapShape::Ary(arity);
+        array.shaped_heap(shape, start, end, end);
+        Self::build_max_heap(array, arity, start, end);
+        for last in (start + 1..end).rev() {
+            array.focus(start, last + 1);
+            array.swap(start, last);
+            array.shaped_heap(shape, start, last, end);
+            Self::sift_down_ary(array, HeapKind::Max, arity, start, 0, last);
         }
-        self.graph.display_graph();
+        array.shaped_heap(shape, start, start, start);
     }
     pub fn get_parent(&self, position: usize) -> i32 {
         let parent_loc = (position) / 2;
@@ -143,7 +396,7 @@ impl<'a, 'b> Heap<'a, 'b> {
         (position) / 2
     }
     pub fn get_sibling(&self, position: usize) -> i32 {
-        if position % 2 == 0 {
+        if position.is_multiple_of(2) {
             self.nodes[position - 1]
         } else {
             self.nodes[position + 1]
@@ -215,7 +468,7 @@ impl<'a, 'b> Heap<'a, 'b> {
     pub fn size(&self) -> usize {
         self.nodes.len()
     }
-    pub fn heapify(vals: &[i32], graph: &'a mut SortGraph<'b, 'b>) -> Self {
+    pub fn heapify(vals: &[i32]) -> Self {
         let mut nodes = Vec::with_capacity(vals.len());
         for (idx, val) in vals.iter().enumerate() {
             nodes.push(*val);
@@ -226,7 +479,9 @@ impl<'a, 'b> Heap<'a, 'b> {
                 nodes.swap(0, idx);
             }
         }
-        graph.values = nodes.clone();
-        Self { graph, nodes }
+        Self {
+            nodes: SortRecorder::new(nodes),
+            kind: HeapKind::Max,
+        }
     }
 }
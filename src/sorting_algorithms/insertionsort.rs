@@ -0,0 +1,57 @@
+use crate::sorting_algorithms::{Complexity, SortInfo, SortRecorder, Sorter};
+use std::cmp::Ordering;
+
+#[derive(Default)]
+pub struct InsertionSort;
+
+impl Sorter for InsertionSort {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "insertionsort",
+            title: "Insertion Sort Algorithm",
+            stable: true,
+            in_place: true,
+            complexity: Complexity {
+                best: "O(n)",
+                average: "O(n^2)",
+                worst: "O(n^2)",
+                space: "O(1)",
+            },
+        }
+    }
+
+    fn sort(&mut self, array: &mut SortRecorder) {
+        for i in 1..array.len() {
+            array.sorted(0, i);
+            Self::insert(array, 0, i);
+        }
+        array.sorted(0, array.len());
+    }
+}
+
+impl InsertionSort {
+    pub fn new() -> Self {
+        InsertionSort
+    }
+    /// Insertion sorts `start..end`, for sorts that finish small ranges this way.
+    pub fn sort_range(array: &mut SortRecorder, start: usize, end: usize) {
+        for i in start + 1..end {
+            Self::insert(array, start, i);
+        }
+    }
+    // Moves the value at `i` into place in the sorted range `start..i`
+    fn insert(array: &mut SortRecorder, start: usize, i: usize) {
+        // Lift the key out and shift larger values right until its slot opens up
+        let key = array[i];
+        let mut hole = i;
+        array.current(hole);
+        while hole > start
+            && array.compare_values(hole - 1, array[hole - 1], hole, key) == Ordering::Greater
+        {
+            array.write(hole, array[hole - 1]);
+            hole -= 1;
+            array.current(hole);
+        }
+        array.write(hole, key);
+    }
+}
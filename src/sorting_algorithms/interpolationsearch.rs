@@ -0,0 +1,52 @@
+use crate::sorting_algorithms::{Complexity, SearchInfo, Searcher, SortRecorder};
+use std::cmp::Ordering;
+
+/// Interpolation search. Instead of the middle, each probe is where the target would be if
+/// the values in range rose evenly from one end to the other, which takes few probes on
+/// evenly spread values and degrades to a linear scan on skewed ones.
+#[derive(Default)]
+pub struct InterpolationSearch;
+
+impl Searcher for InterpolationSearch {
+    fn info(&self) -> SearchInfo {
+        SearchInfo {
+            name: "interpolationsearch",
+            title: "Interpolation Search Algorithm",
+            complexity: Complexity {
+                best: "O(1)",
+                average: "O(log log n)",
+                worst: "O(n)",
+                space: "O(1)",
+            },
+        }
+    }
+
+    fn search(&mut self, array: &mut SortRecorder, target: i32) -> Option<usize> {
+        let (mut low, mut high) = (0, array.len());
+        let key = array.key_of(target) as i64;
+        while low < high {
+            array.focus(low, high);
+            // The end values only place the probe, so reading them is not counted as probing
+            let (first, last) = (array.key(low) as i64, array.key(high - 1) as i64);
+            let span = (high - 1 - low) as i64;
+            let offset = match last > first {
+                true => ((key - first) * span / (last - first)).clamp(0, span),
+                false => 0,
+            };
+            let probe = low + offset as usize;
+            match array.probe(probe, target) {
+                Ordering::Less => low = probe + 1,
+                Ordering::Greater => high = probe,
+                Ordering::Equal => return Some(probe),
+            }
+        }
+        array.focus(low, high);
+        None
+    }
+}
+
+impl InterpolationSearch {
+    pub fn new() -> Self {
+        InterpolationSearch
+    }
+}
@@ -0,0 +1,82 @@
+use crate::sorting_algorithms::{Complexity, Heap, InsertionSort, SortInfo, SortRecorder, Sorter};
+
+// Ranges this small are left for a final insertion sort
+const INSERTION_THRESHOLD: usize = 8;
+
+/// Quicksort that falls back to `Heap` sorting once the recursion gets deeper than
+/// 2 log2(n), which caps the worst case at O(n log n). Small ranges are finished with
+/// insertion sort, as in the C++ standard library.
+#[derive(Default)]
+pub struct IntroSort;
+
+impl Sorter for IntroSort {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "introsort",
+            title: "Introsort Algorithm",
+            stable: false,
+            in_place: true,
+            complexity: Complexity {
+                best: "O(n log n)",
+                average: "O(n log n)",
+                worst: "O(n log n)",
+                space: "O(log n)",
+            },
+        }
+    }
+
+    fn sort(&mut self, array: &mut SortRecorder) {
+        let len = array.len();
+        if len <= 1 {
+            return;
+        }
+        let depth_limit = 2 * len.ilog2() as usize;
+        self.introsort(array, 0, len, depth_limit);
+        array.phase("insertion sort");
+        array.focus(0, len);
+        InsertionSort::sort_range(array, 0, len);
+    }
+}
+
+impl IntroSort {
+    pub fn new() -> Self {
+        IntroSort
+    }
+    fn introsort(&mut self, array: &mut SortRecorder, start: usize, end: usize, depth: usize) {
+        if end - start <= INSERTION_THRESHOLD {
+            return;
+        }
+        if depth == 0 {
+            array.phase("heapsort fallback");
+            Heap::heapsort_range(array, start, end);
+            return;
+        }
+        array.phase("quicksort");
+        let pivot = Self::partition(array, start, end);
+        self.introsort(array, start, pivot, depth - 1);
+        self.introsort(array, pivot + 1, end, depth - 1);
+    }
+    // Lomuto partition around the median of the first, middle and last values
+    fn partition(array: &mut SortRecorder, start: usize, end: usize) -> usize {
+        let (first, middle, last) = (start, start + (end - start) / 2, end - 1);
+        // Order the three candidates, leaving the median in the middle
+        for (i, j) in [(first, middle), (middle, last), (first, middle)] {
+            if array.less(j, i) {
+                array.swap(i, j);
+            }
+        }
+        array.swap(middle, last);
+
+        array.focus(start, end);
+        array.pivot(last);
+        let mut store = start;
+        for j in start..last {
+            if array.less(j, last) {
+                array.swap(store, j);
+                store += 1;
+            }
+        }
+        array.swap(store, last);
+        store
+    }
+}
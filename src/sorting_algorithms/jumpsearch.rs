@@ -0,0 +1,59 @@
+use crate::sorting_algorithms::{Complexity, SearchInfo, Searcher, SortRecorder};
+use std::cmp::Ordering;
+
+/// Jump search. Probes the last value of each block of about the square root of the length
+/// until one is not below the target, then scans that block from its start.
+#[derive(Default)]
+pub struct JumpSearch;
+
+impl Searcher for JumpSearch {
+    fn info(&self) -> SearchInfo {
+        SearchInfo {
+            name: "jumpsearch",
+            title: "Jump Search Algorithm",
+            complexity: Complexity {
+                best: "O(1)",
+                average: "O(sqrt n)",
+                worst: "O(sqrt n)",
+                space: "O(1)",
+            },
+        }
+    }
+
+    fn search(&mut self, array: &mut SortRecorder, target: i32) -> Option<usize> {
+        let len = array.len();
+        let step = len.isqrt().max(1);
+        let mut start = 0;
+        array.phase("jumping");
+        let end = loop {
+            if start >= len {
+                array.focus(len, len);
+                return None;
+            }
+            array.focus(start, len);
+            let end = (start + step).min(len);
+            match array.probe(end - 1, target) {
+                Ordering::Less => start = end,
+                Ordering::Equal => return Some(end - 1),
+                Ordering::Greater => break end - 1,
+            }
+        };
+        array.phase("scanning");
+        for idx in start..end {
+            array.focus(idx, end);
+            match array.probe(idx, target) {
+                Ordering::Less => {}
+                Ordering::Equal => return Some(idx),
+                Ordering::Greater => break,
+            }
+        }
+        array.focus(end, end);
+        None
+    }
+}
+
+impl JumpSearch {
+    pub fn new() -> Self {
+        JumpSearch
+    }
+}
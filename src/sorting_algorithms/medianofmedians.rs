@@ -0,0 +1,88 @@
+use crate::sorting_algorithms::{Complexity, QuickSort, Selector, SortInfo, SortRecorder};
+
+// Values per group whose medians are gathered
+const GROUP: i32 = 5;
+
+/// Blum, Floyd, Pratt, Rivest and Tarjan's deterministic selection. The pivot is the median of
+/// the medians of groups of five, found by selecting recursively, which guarantees each
+/// partition discards at least 30% of the range.
+#[derive(Default)]
+pub struct MedianOfMedians;
+
+impl Selector for MedianOfMedians {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "medianofmedians",
+            title: "Median of Medians Selection Algorithm",
+            stable: false,
+            in_place: true,
+            complexity: Complexity {
+                best: "O(n)",
+                average: "O(n)",
+                worst: "O(n)",
+                space: "O(log n)",
+            },
+        }
+    }
+
+    fn select(&mut self, array: &mut SortRecorder, k: usize) {
+        let high = array.len() as i32 - 1;
+        Self::select_range(array, 0, high, k as i32);
+        array.selected(k);
+    }
+}
+
+impl MedianOfMedians {
+    pub fn new() -> Self {
+        MedianOfMedians
+    }
+    /// Moves the value of rank `k` within `low..=high` to index `k`, partitioning around
+    /// median-of-medians pivots. Does not mark it, so quickselect variants can fall back on it.
+    pub fn select_range(array: &mut SortRecorder, mut low: i32, mut high: i32, k: i32) {
+        while low < high {
+            array.focus(low as usize, high as usize + 1);
+            let chosen = Self::pivot(array, low, high);
+            array.focus(low as usize, high as usize + 1);
+            // Values equal to the pivot are set aside together, so duplicates cannot slow it down
+            let (first, last) = QuickSort::three_way_partition_around(array, low, high, chosen);
+            if k < first {
+                high = first - 1;
+            } else if k > last {
+                low = last + 1;
+            } else {
+                return;
+            }
+        }
+    }
+    // Gathers the median of each group of five at the front of `low..=high` and returns the
+    // index of the median of those medians
+    fn pivot(array: &mut SortRecorder, low: i32, high: i32) -> i32 {
+        array.phase("median of medians");
+        if high - low < GROUP {
+            return Self::median_of_group(array, low, high);
+        }
+        let mut groups = 0;
+        for start in (low..=high).step_by(GROUP as usize) {
+            let median = Self::median_of_group(array, start, (start + GROUP - 1).min(high));
+            if median != low + groups {
+                array.swap(median as usize, (low + groups) as usize);
+            }
+            groups += 1;
+        }
+        let middle = low + (groups - 1) / 2;
+        Self::select_range(array, low, low + groups - 1, middle);
+        array.phase("median of medians");
+        middle
+    }
+    // Insertion sorts a group of at most five and returns the index of its median
+    fn median_of_group(array: &mut SortRecorder, low: i32, high: i32) -> i32 {
+        for i in low + 1..=high {
+            let mut j = i;
+            while j > low && array.less(j as usize, (j - 1) as usize) {
+                array.swap(j as usize, (j - 1) as usize);
+                j -= 1;
+            }
+        }
+        low + (high - low) / 2
+    }
+}
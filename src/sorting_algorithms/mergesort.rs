@@ -1,73 +1,297 @@
-use crate::sorting_algorithms::SortGraph;
+use crate::sorting_algorithms::{Complexity, SortInfo, SortRecorder, Sorter};
+use std::cmp::Ordering;
 
-pub struct MergeSort<'a, 'b> {
-    pub graph: &'a mut SortGraph<'b, 'b>,
-    pub values: Vec<i32>,
+#[derive(Default)]
+pub struct MergeSort;
+
+impl Sorter for MergeSort {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "mergesort",
+            title: "Merge Sort Algorithm",
+            stable: true,
+            in_place: false,
+            complexity: Complexity {
+                best: "O(n log n)",
+                average: "O(n log n)",
+                worst: "O(n log n)",
+                space: "O(n)",
+            },
+        }
+    }
+
+    fn sort(&mut self, array: &mut SortRecorder) {
+        let len = array.len();
+        self.top_down_split_merge(array, 0, len);
+    }
 }
 
-impl<'a, 'b> MergeSort<'a, 'b> {
-    pub fn new(graph: &'a mut SortGraph<'b, 'b>) -> Self {
-        let values = graph.values.clone();
-        MergeSort {
-            graph,
-            values: values.to_vec(),
-        }
-    }
-    pub fn sort(&mut self) {
-        self.graph.display_graph();
-        let mut work_array = self.values.clone(); // Temporary array for sorting
-        let len = self.values.len(); // Store the length to avoid borrowing issues
-        self.top_down_split_merge(&mut work_array, 0, len);
-        self.graph.values = self.values.clone();
-        self.graph.display_graph();
-    }
-
-    fn top_down_split_merge(
-        &mut self,
-        dst: &mut [i32], // Destination array
-        begin: usize,
-        end: usize,
-    ) {
+impl MergeSort {
+    pub fn new() -> Self {
+        MergeSort
+    }
+
+    fn top_down_split_merge(&mut self, array: &mut SortRecorder, begin: usize, end: usize) {
         if end - begin <= 1 {
             return; // Run size == 1, consider it sorted
         }
 
         let middle = (begin + end) / 2;
         // Recursively sort both halves
-        self.top_down_split_merge(dst, begin, middle); // Swap roles of src and dst
-        self.top_down_split_merge(dst, middle, end); // Swap roles of src and dst
+        self.top_down_split_merge(array, begin, middle);
+        self.top_down_split_merge(array, middle, end);
+
+        merge(array, begin, middle, end);
+    }
+}
+
+/// Merge sort without recursion. Runs of width 1, 2, 4, ... are merged in pairs from left
+/// to right until one run covers the array.
+#[derive(Default)]
+pub struct BottomUpMergeSort;
+
+impl Sorter for BottomUpMergeSort {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "bottomupmergesort",
+            title: "Bottom-Up Merge Sort Algorithm",
+            stable: true,
+            in_place: false,
+            complexity: Complexity {
+                best: "O(n log n)",
+                average: "O(n log n)",
+                worst: "O(n log n)",
+                space: "O(n)",
+            },
+        }
+    }
+
+    fn sort(&mut self, array: &mut SortRecorder) {
+        let len = array.len();
+        let mut width = 1;
+        while width < len {
+            for begin in (0..len).step_by(2 * width) {
+                let middle = (begin + width).min(len);
+                let end = (begin + 2 * width).min(len);
+                if middle < end {
+                    merge(array, begin, middle, end);
+                }
+            }
+            width *= 2;
+        }
+    }
+}
+
+impl BottomUpMergeSort {
+    pub fn new() -> Self {
+        BottomUpMergeSort
+    }
+}
 
-        self.top_down_merge(dst, begin, middle, end);
+/// Merge sort over the runs already in the input. Ascending runs are found in one scan and
+/// merged in pairs, so sorted input takes a single pass.
+#[derive(Default)]
+pub struct NaturalMergeSort;
+
+impl Sorter for NaturalMergeSort {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "naturalmergesort",
+            title: "Natural Merge Sort Algorithm",
+            stable: true,
+            in_place: false,
+            complexity: Complexity {
+                best: "O(n)",
+                average: "O(n log n)",
+                worst: "O(n log n)",
+                space: "O(n)",
+            },
+        }
     }
 
-    fn top_down_merge(
-        &mut self,
-        dst: &mut [i32], // Destination array
-        begin: usize,
-        middle: usize,
-        end: usize,
-    ) {
-        let mut i = begin; // index of left side start
-        let mut j = middle; // index of right side start
+    fn sort(&mut self, array: &mut SortRecorder) {
+        let len = array.len();
+        // Ends of the runs, in array order
+        let mut ends = Vec::new();
+        let mut start = 0;
+        while start < len {
+            let mut end = start + 1;
+            while end < len && !array.less(end, end - 1) {
+                end += 1;
+            }
+            array.run(start, end);
+            ends.push(end);
+            start = end;
+        }
 
-        for (k, val) in dst.iter_mut().enumerate().take(begin).skip(end) {
-            if i < middle && (j >= end || self.values[i] <= self.values[j]) {
-                *val = self.values[i];
-                self.graph
-                    .display_graph_move_highlights(begin, middle, end, Some((i, k)));
+        while ends.len() > 1 {
+            let mut merged = Vec::new();
+            let mut begin = 0;
+            for pair in ends.chunks(2) {
+                if let [middle, end] = *pair {
+                    merge(array, begin, middle, end);
+                }
+                let end = pair[pair.len() - 1];
+                merged.push(end);
+                begin = end;
+            }
+            ends = merged;
+        }
+    }
+}
 
-                i += 1;
+impl NaturalMergeSort {
+    pub fn new() -> Self {
+        NaturalMergeSort
+    }
+}
+
+/// Top-down merge sort that merges without a buffer. The longer run is cut in half, the
+/// matching cut in the other run is found by binary search, and the two middle pieces trade
+/// places with a rotation before each side is merged the same way.
+#[derive(Default)]
+pub struct InPlaceMergeSort;
+
+impl Sorter for InPlaceMergeSort {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "inplacemergesort",
+            title: "In-Place Merge Sort Algorithm",
+            stable: true,
+            in_place: true,
+            complexity: Complexity {
+                best: "O(n log n)",
+                average: "O(n log^2 n)",
+                worst: "O(n log^2 n)",
+                space: "O(log n)",
+            },
+        }
+    }
+
+    fn sort(&mut self, array: &mut SortRecorder) {
+        let len = array.len();
+        Self::split_merge(array, 0, len);
+    }
+}
+
+impl InPlaceMergeSort {
+    pub fn new() -> Self {
+        InPlaceMergeSort
+    }
+    fn split_merge(array: &mut SortRecorder, begin: usize, end: usize) {
+        if end - begin <= 1 {
+            return;
+        }
+        let middle = (begin + end) / 2;
+        Self::split_merge(array, begin, middle);
+        Self::split_merge(array, middle, end);
+        array.split(begin, middle, end);
+        array.phase("merging");
+        Self::merge(array, begin, middle, end);
+    }
+    // Merges the sorted runs `begin..middle` and `middle..end` in place
+    fn merge(array: &mut SortRecorder, begin: usize, middle: usize, end: usize) {
+        if begin == middle || middle == end {
+            return;
+        }
+        if end - begin == 2 {
+            if array.less(middle, begin) {
+                array.swap(begin, middle);
+            }
+            return;
+        }
+        // Values left of `left_cut` and right of `right_cut` are already on the correct side
+        let (left_cut, right_cut) = if middle - begin >= end - middle {
+            let left_cut = begin + (middle - begin) / 2;
+            (left_cut, Self::lower_bound(array, middle, end, left_cut))
+        } else {
+            let right_cut = middle + (end - middle) / 2;
+            (
+                Self::upper_bound(array, begin, middle, right_cut),
+                right_cut,
+            )
+        };
+        array.phase("rotating");
+        Self::rotate(array, left_cut, middle, right_cut);
+        array.phase("merging");
+        let new_middle = left_cut + (right_cut - middle);
+        Self::merge(array, begin, left_cut, new_middle);
+        Self::merge(array, new_middle, right_cut, end);
+    }
+    // First index in `start..end` whose value is not less than the value at `target`
+    fn lower_bound(
+        array: &mut SortRecorder,
+        mut start: usize,
+        mut end: usize,
+        target: usize,
+    ) -> usize {
+        while start < end {
+            let middle = start + (end - start) / 2;
+            if array.less(middle, target) {
+                start = middle + 1;
+            } else {
+                end = middle;
+            }
+        }
+        start
+    }
+    // First index in `start..end` whose value is greater than the value at `target`
+    fn upper_bound(
+        array: &mut SortRecorder,
+        mut start: usize,
+        mut end: usize,
+        target: usize,
+    ) -> usize {
+        while start < end {
+            let middle = start + (end - start) / 2;
+            if array.less(target, middle) {
+                end = middle;
             } else {
-                *val = self.values[j];
-                self.graph
-                    .display_graph_move_highlights(begin, middle, end, Some((j, k)));
-                j += 1;
+                start = middle + 1;
             }
         }
-        // Copy the sorted elements back to the original array
-        self.values[begin..end].copy_from_slice(&dst[begin..end]);
-        self.graph.values = self.values.clone();
-        self.graph
-            .display_graph_move_highlights(begin, middle, end, None);
+        start
+    }
+    // Swaps the blocks `start..middle` and `middle..end` by reversing each and then both
+    fn rotate(array: &mut SortRecorder, start: usize, middle: usize, end: usize) {
+        if start == middle || middle == end {
+            return;
+        }
+        Self::reverse(array, start, middle);
+        Self::reverse(array, middle, end);
+        Self::reverse(array, start, end);
+    }
+    fn reverse(array: &mut SortRecorder, mut start: usize, mut end: usize) {
+        while start + 1 < end {
+            end -= 1;
+            array.swap(start, end);
+            start += 1;
+        }
+    }
+}
+
+// Merges the sorted runs `begin..middle` and `middle..end`, moving both out into the
+// auxiliary buffer so the merged output can be written straight back into the array
+fn merge(array: &mut SortRecorder, begin: usize, middle: usize, end: usize) {
+    array.split(begin, middle, end);
+    array.alloc(end - begin);
+    for idx in begin..end {
+        array.stash(idx, idx);
+    }
+    let mut i = begin; // index of left side start
+    let mut j = middle; // index of right side start
+
+    for k in begin..end {
+        let take_left = i < middle
+            && (j >= end
+                || array.compare_values(i, array.aux(i), j, array.aux(j)) != Ordering::Greater);
+        if take_left {
+            array.unstash(i, k);
+            i += 1;
+        } else {
+            array.unstash(j, k);
+            j += 1;
+        }
     }
+    array.free(end - begin);
 }
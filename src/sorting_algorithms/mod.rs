@@ -1,9 +1,95 @@
 // Automatically generated mod.rs
+pub mod binaryinsertionsort;
+pub mod binarysearch;
+pub mod bogosort;
+pub mod bubblesort;
+pub mod bucketsort;
+pub mod cocktailsort;
+pub mod combsort;
+pub mod countingsort;
+pub mod cyclesort;
+pub mod distribution;
+pub mod exponentialsearch;
+pub mod gnomesort;
+pub mod heap_tree;
 pub mod heapsort;
-pub use heapsort::Heap;
+pub mod insertionsort;
+pub mod interpolationsearch;
+pub mod introsort;
+pub mod jumpsearch;
+pub use binaryinsertionsort::BinaryInsertionSort;
+pub use binarysearch::BinarySearch;
+pub use bogosort::BogoSort;
+pub use bubblesort::BubbleSort;
+pub use bucketsort::BucketSort;
+pub use cocktailsort::CocktailShakerSort;
+pub use combsort::CombSort;
+pub use countingsort::CountingSort;
+pub use cyclesort::CycleSort;
+pub use distribution::{distribution_names, find_distribution, Distribution};
+pub use exponentialsearch::ExponentialSearch;
+pub use gnomesort::GnomeSort;
+pub use heap_tree::{HeapShape, HeapTree};
+pub use heapsort::{DaryHeapSort, Heap, HeapError, HeapKind};
+pub use insertionsort::InsertionSort;
+pub use interpolationsearch::InterpolationSearch;
+pub use introsort::IntroSort;
+pub use jumpsearch::JumpSearch;
+pub mod medianofmedians;
 pub mod mergesort;
-pub use mergesort::MergeSort;
+pub mod network_diagram;
+pub mod oddevensort;
+pub mod pancakesort;
+pub mod partialsort;
+pub mod pdqsort;
+pub use medianofmedians::MedianOfMedians;
+pub use mergesort::{BottomUpMergeSort, InPlaceMergeSort, MergeSort, NaturalMergeSort};
+pub use network_diagram::NetworkDiagram;
+pub use oddevensort::OddEvenSort;
+pub use pancakesort::PancakeSort;
+pub use partialsort::PartialSort;
+pub use pdqsort::PdqSort;
+pub mod priority_queue;
+pub mod quickselect;
 pub mod quicksort;
-pub use quicksort::QuickSort;
+pub mod radixsort;
+pub mod searcher;
+pub mod selectionsort;
+pub mod selector;
+pub use priority_queue::HeapOp;
+pub use quickselect::{IntroSelect, QuickSelect};
+pub use quicksort::{PartitionScheme, PivotRule, QuickSort};
+pub use radixsort::{LsdRadixSort, MsdRadixSort};
+pub use searcher::{find_searcher, searcher_names, searchers, SearchCheck, SearchInfo, Searcher};
+pub use selectionsort::SelectionSort;
+pub use selector::{
+    find_selector, find_selector_with, selector_names, selectors, selectors_with, SelectionCheck,
+    Selector,
+};
+pub mod shellsort;
+pub mod smoothsort;
+pub mod sorter;
+pub use shellsort::{GapSequence, ShellSort};
+pub use smoothsort::SmoothSort;
+pub use sorter::{
+    find_sorter, find_sorter_with, sorter_names, sorters, sorters_with, Complexity, SortInfo,
+    Sorter, SorterOptions,
+};
 pub mod sorting_graph;
-pub use sorting_graph::SortGraph;
+pub mod sorting_network;
+pub mod stats;
+pub mod stoogesort;
+pub mod ternarysearch;
+pub mod timsort;
+pub use sorting_graph::{SortConfig, SortGraph};
+pub use sorting_network::{BitonicSort, OddEvenMergeSort};
+pub mod trace;
+pub mod verification;
+pub use stats::SortStats;
+pub use stoogesort::StoogeSort;
+pub use ternarysearch::TernarySearch;
+pub use timsort::TimSort;
+pub use trace::{SortEvent, SortRecorder, SortTrace};
+pub use verification::Verification;
+pub mod weakheapsort;
+pub use weakheapsort::WeakHeapSort;
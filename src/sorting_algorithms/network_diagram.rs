@@ -0,0 +1,151 @@
+use crate::sorting_algorithms::{SortEvent, SortTrace};
+use colored::{Color, Colorize};
+
+/// Draws the comparator network of a sorting network run under the bars. Every bar is
+/// continued downwards by its wire, and each comparator joins two wires with a horizontal
+/// line. Comparators of one layer that would overlap are spread over several lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkDiagram {
+    pub layers: Vec<Vec<(usize, usize)>>, // Comparators of each layer, lower wire first
+    lines: Vec<(usize, Vec<(usize, usize)>)>, // Comparators drawn on each line, and their layer
+}
+
+impl NetworkDiagram {
+    /// Collects the network from the `Layer` events of `trace`, taking the compares that follow
+    /// each one as its comparators. Returns None when the run is not a sorting network.
+    pub fn from_trace(trace: &SortTrace) -> Option<Self> {
+        let mut layers: Vec<Vec<(usize, usize)>> = Vec::new();
+        for event in trace.events.iter() {
+            match *event {
+                SortEvent::Layer(_) => layers.push(Vec::new()),
+                SortEvent::Compare(i, j) => {
+                    if let Some(layer) = layers.last_mut() {
+                        layer.push((i.min(j), i.max(j)));
+                    }
+                }
+                _ => {}
+            }
+        }
+        if layers.is_empty() {
+            return None;
+        }
+
+        // Comparators sorted by their lower wire go on the first line they do not overlap
+        let mut lines = Vec::new();
+        for (idx, layer) in layers.iter().enumerate() {
+            let mut comparators = layer.clone();
+            comparators.sort_unstable();
+            let first_line = lines.len();
+            for (low, high) in comparators {
+                let free = lines[first_line..].iter().position(
+                    |(_, line): &(usize, Vec<(usize, usize)>)| {
+                        line.last().is_none_or(|&(_, end)| end < low)
+                    },
+                );
+                match free {
+                    Some(offset) => lines[first_line + offset].1.push((low, high)),
+                    None => lines.push((idx, vec![(low, high)])),
+                }
+            }
+        }
+        Some(Self { layers, lines })
+    }
+
+    /// Number of lines needed to draw the whole network.
+    pub fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Returns `rows` lines drawing the part of the network around `layer`, with `cell`
+    /// characters per wire to line up with the bars. Layers already run are dimmed, the active
+    /// one is cyan, and the comparator `event` works on is yellow while it compares and red
+    /// when it swaps.
+    pub fn lines(
+        &self,
+        wires: usize,
+        layer: Option<usize>,
+        event: &SortEvent,
+        cell: usize,
+        rows: usize,
+    ) -> Vec<String> {
+        // Scroll so the active layer starts a quarter of the way down, when it does not all fit
+        let active_line = layer
+            .and_then(|layer| self.lines.iter().position(|(idx, _)| *idx == layer))
+            .unwrap_or(0);
+        let first = active_line
+            .saturating_sub(rows / 4)
+            .min(self.lines.len().saturating_sub(rows));
+
+        let working = match *event {
+            SortEvent::Compare(i, j) => Some(((i.min(j), i.max(j)), Color::BrightYellow)),
+            SortEvent::Swap(i, j) => Some(((i.min(j), i.max(j)), Color::BrightRed)),
+            _ => None,
+        };
+        (first..first + rows)
+            .map(|line| match self.lines.get(line) {
+                Some((idx, comparators)) => {
+                    let active = layer == Some(*idx);
+                    let color = match layer {
+                        Some(layer) if *idx < layer => Some(Color::BrightBlack),
+                        _ if active => Some(Color::Cyan),
+                        _ => None,
+                    };
+                    let label = match self.lines.get(line.wrapping_sub(1)) {
+                        Some((previous, _)) if previous == idx => String::new(),
+                        _ => paint(&format!(" layer {}", idx + 1), color),
+                    };
+                    let mut text =
+                        Self::line(wires, comparators, cell, |comparator| match working {
+                            Some((worked, working)) if active && worked == comparator => {
+                                Some(working)
+                            }
+                            _ => color,
+                        });
+                    text += &label;
+                    text
+                }
+                None => String::new(),
+            })
+            .collect()
+    }
+
+    // Draws the wires and the comparators of one line, colored by `color`
+    fn line(
+        wires: usize,
+        comparators: &[(usize, usize)],
+        cell: usize,
+        color: impl Fn((usize, usize)) -> Option<Color>,
+    ) -> String {
+        let mut text = String::new();
+        let mut comparators = comparators.iter().copied().peekable();
+        for wire in 0..wires {
+            while comparators.next_if(|&(_, high)| high < wire).is_some() {}
+            let symbol = match comparators.peek() {
+                Some(&(low, high)) if wire == low => {
+                    Some((if cell >= 3 { " ●─" } else { "●" }, color((low, high))))
+                }
+                Some(&(low, high)) if wire == high => {
+                    Some((if cell >= 3 { "─● " } else { "●" }, color((low, high))))
+                }
+                Some(&(low, high)) if wire > low => {
+                    Some((if cell >= 3 { "─┼─" } else { "┼" }, color((low, high))))
+                }
+                _ => None,
+            };
+            text += &match symbol {
+                Some((symbol, color)) => paint(symbol, color),
+                None if cell >= 3 => " │ ".bright_black().to_string(),
+                None => "│".bright_black().to_string(),
+            };
+        }
+        text
+    }
+}
+
+// Colors `text`, or leaves it in the terminal's own color
+fn paint(text: &str, color: Option<Color>) -> String {
+    match color {
+        Some(color) => text.color(color).to_string(),
+        None => text.to_string(),
+    }
+}
@@ -0,0 +1,49 @@
+use crate::sorting_algorithms::{Complexity, SortInfo, SortRecorder, Sorter};
+use std::cmp::Ordering;
+
+/// Odd-even transposition sort, also called brick sort. Passes alternate between comparing
+/// the pairs starting at odd and at even positions. The pairs of a pass do not overlap, so
+/// on parallel hardware each pass takes one step.
+#[derive(Default)]
+pub struct OddEvenSort;
+
+impl Sorter for OddEvenSort {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "oddevensort",
+            title: "Odd-Even Transposition Sort Algorithm",
+            stable: true,
+            in_place: true,
+            complexity: Complexity {
+                best: "O(n)",
+                average: "O(n^2)",
+                worst: "O(n^2)",
+                space: "O(1)",
+            },
+        }
+    }
+
+    fn sort(&mut self, array: &mut SortRecorder) {
+        let len = array.len();
+        let mut swapped = true;
+        while swapped {
+            swapped = false;
+            for (first, phase) in [(1, "odd pairs"), (0, "even pairs")] {
+                array.phase(phase);
+                for i in (first..len.saturating_sub(1)).step_by(2) {
+                    if array.compare(i, i + 1) == Ordering::Greater {
+                        array.swap(i, i + 1);
+                        swapped = true;
+                    }
+                }
+            }
+        }
+        array.sorted(0, len);
+    }
+}
+
+impl OddEvenSort {
+    pub fn new() -> Self {
+        OddEvenSort
+    }
+}
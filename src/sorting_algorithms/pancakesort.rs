@@ -0,0 +1,66 @@
+use crate::sorting_algorithms::{Complexity, SortInfo, SortRecorder, Sorter};
+
+/// Pancake sort may only reverse a prefix of the array, like flipping the top of a stack of
+/// pancakes with a spatula. The largest unsorted value is flipped to the front, then the
+/// whole unsorted part is flipped to send it to the back.
+#[derive(Default)]
+pub struct PancakeSort;
+
+impl Sorter for PancakeSort {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "pancakesort",
+            title: "Pancake Sort Algorithm",
+            stable: false,
+            in_place: true,
+            complexity: Complexity {
+                best: "O(n^2)",
+                average: "O(n^2)",
+                worst: "O(n^2)",
+                space: "O(1)",
+            },
+        }
+    }
+
+    fn sort(&mut self, array: &mut SortRecorder) {
+        let len = array.len();
+        for size in (2..=len).rev() {
+            array.sorted(size, len);
+            array.phase("finding the largest");
+            array.focus(0, size);
+            let mut max = 0;
+            array.candidate(max);
+            for i in 1..size {
+                if array.less(max, i) {
+                    max = i;
+                    array.candidate(max);
+                }
+            }
+            if max == size - 1 {
+                continue;
+            }
+            if max > 0 {
+                Self::flip(array, max + 1);
+            }
+            Self::flip(array, size);
+        }
+        array.sorted(0, len);
+    }
+}
+
+impl PancakeSort {
+    pub fn new() -> Self {
+        PancakeSort
+    }
+    // Reverses the first `count` values, the spatula's reach shown as the focus
+    fn flip(array: &mut SortRecorder, count: usize) {
+        array.phase("flipping");
+        array.focus(0, count);
+        let (mut low, mut high) = (0, count - 1);
+        while low < high {
+            array.swap(low, high);
+            low += 1;
+            high -= 1;
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use crate::sorting_algorithms::{Complexity, Heap, HeapKind, Selector, SortInfo, SortRecorder};
+
+/// Partial sort with a heap, the top-k half of heapsort. A max-heap at the front holds the
+/// k smallest values seen so far, and each later value smaller than its root replaces it.
+/// The root then is the k-th smallest, and it stops as soon as that is swapped into place.
+#[derive(Default)]
+pub struct PartialSort;
+
+impl Selector for PartialSort {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "partialsort",
+            title: "Partial Sort Algorithm (top k with a heap)",
+            stable: false,
+            in_place: true,
+            complexity: Complexity {
+                best: "O(n log k)",
+                average: "O(n log k)",
+                worst: "O(n log k)",
+                space: "O(1)",
+            },
+        }
+    }
+
+    fn select(&mut self, array: &mut SortRecorder, k: usize) {
+        let len = array.len();
+        if len == 0 {
+            return;
+        }
+        // A rank past the end keeps every value in the heap, and has nothing to swap into place
+        let heap_len = (k + 1).min(len);
+        array.phase("keeping the k smallest");
+        array.heap(0, heap_len, heap_len);
+        for idx in (0..heap_len / 2).rev() {
+            Heap::sift_down(array, HeapKind::Max, idx, heap_len);
+        }
+        for idx in heap_len..len {
+            if array.less(idx, 0) {
+                array.swap(0, idx);
+                Heap::sift_down(array, HeapKind::Max, 0, heap_len);
+            }
+        }
+        // The largest of the k smallest is the k-th, and its place is the end of the heap
+        if k > 0 && k < len {
+            array.swap(0, k);
+        }
+        array.heap(0, 0, 0);
+        array.selected(k);
+    }
+}
+
+impl PartialSort {
+    pub fn new() -> Self {
+        PartialSort
+    }
+}
@@ -0,0 +1,225 @@
+use crate::sorting_algorithms::{Complexity, Heap, InsertionSort, SortInfo, SortRecorder, Sorter};
+use std::cmp::Ordering;
+
+// Ranges this small are insertion sorted. The reference implementation uses 24 and 128,
+// halved here so the other modes show up on inputs that fit the terminal.
+const INSERTION_THRESHOLD: usize = 12;
+const NINTHER_THRESHOLD: usize = 64;
+// Most values a partial insertion sort may move before giving up
+const PARTIAL_INSERTION_LIMIT: usize = 8;
+
+/// Pattern-defeating quicksort, after Orson Peters' pdqsort and Rust's `sort_unstable`.
+/// It spots already partitioned ranges and runs of equal values, shuffles a few values
+/// after badly unbalanced partitions, and falls back to `Heap` sorting when that keeps
+/// happening.
+#[derive(Default)]
+pub struct PdqSort;
+
+impl Sorter for PdqSort {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "pdqsort",
+            title: "Pattern-Defeating Quicksort Algorithm",
+            stable: false,
+            in_place: true,
+            complexity: Complexity {
+                best: "O(n)",
+                average: "O(n log n)",
+                worst: "O(n log n)",
+                space: "O(log n)",
+            },
+        }
+    }
+
+    fn sort(&mut self, array: &mut SortRecorder) {
+        let len = array.len();
+        if len <= 1 {
+            return;
+        }
+        self.pdq_loop(array, 0, len, len.ilog2() as usize, true);
+    }
+}
+
+impl PdqSort {
+    pub fn new() -> Self {
+        PdqSort
+    }
+    fn pdq_loop(
+        &mut self,
+        array: &mut SortRecorder,
+        mut start: usize,
+        end: usize,
+        mut bad_allowed: usize,
+        mut leftmost: bool,
+    ) {
+        loop {
+            let len = end - start;
+            if len < INSERTION_THRESHOLD {
+                array.phase("insertion sort");
+                array.focus(start, end);
+                InsertionSort::sort_range(array, start, end);
+                return;
+            }
+
+            array.phase("choose pivot");
+            array.focus(start, end);
+            Self::choose_pivot(array, start, end);
+
+            // A pivot equal to the value left of the range means every value here is at least
+            // as large, so the values equal to it can be put in place all at once
+            if !leftmost && !array.less(start - 1, start) {
+                array.phase("equal values");
+                start = Self::partition_left(array, start, end) + 1;
+                continue;
+            }
+
+            array.phase("partition");
+            let (pivot, already_partitioned) = Self::partition_right(array, start, end);
+            let (left_len, right_len) = (pivot - start, end - pivot - 1);
+            if left_len < len / 8 || right_len < len / 8 {
+                bad_allowed -= 1;
+                if bad_allowed == 0 {
+                    array.phase("heapsort fallback");
+                    Heap::heapsort_range(array, start, end);
+                    return;
+                }
+                array.phase("pattern breaking");
+                Self::break_patterns(array, start, pivot);
+                Self::break_patterns(array, pivot + 1, end);
+            } else if already_partitioned {
+                array.phase("partial insertion sort");
+                if Self::partial_insertion_sort(array, start, pivot)
+                    && Self::partial_insertion_sort(array, pivot + 1, end)
+                {
+                    return;
+                }
+            }
+
+            self.pdq_loop(array, start, pivot, bad_allowed, leftmost);
+            start = pivot + 1;
+            leftmost = false;
+        }
+    }
+    // Moves the median of three values, or the median of three medians on large ranges,
+    // to `start`
+    fn choose_pivot(array: &mut SortRecorder, start: usize, end: usize) {
+        let (len, middle) = (end - start, start + (end - start) / 2);
+        if len > NINTHER_THRESHOLD {
+            Self::sort3(array, start, middle, end - 1);
+            Self::sort3(array, start + 1, middle - 1, end - 2);
+            Self::sort3(array, start + 2, middle + 1, end - 3);
+            Self::sort3(array, middle - 1, middle, middle + 1);
+            array.swap(start, middle);
+        } else {
+            Self::sort3(array, middle, start, end - 1);
+        }
+    }
+    // Orders the values at `a`, `b` and `c`
+    fn sort3(array: &mut SortRecorder, a: usize, b: usize, c: usize) {
+        for (i, j) in [(a, b), (b, c), (a, b)] {
+            if array.less(j, i) {
+                array.swap(i, j);
+            }
+        }
+    }
+    // Partitions around the pivot at `start`, with values equal to it going right. Returns
+    // where the pivot ends up and whether no values had to be swapped.
+    fn partition_right(array: &mut SortRecorder, start: usize, end: usize) -> (usize, bool) {
+        array.pivot(start);
+        let mut first = start + 1;
+        while first < end && array.less(first, start) {
+            first += 1;
+        }
+        let mut last = end - 1;
+        while last >= first && !array.less(last, start) {
+            last -= 1;
+        }
+        let already_partitioned = first > last;
+
+        // The swapped values stop each scan before it can run past the other
+        while first < last {
+            array.swap(first, last);
+            first += 1;
+            while array.less(first, start) {
+                first += 1;
+            }
+            last -= 1;
+            while !array.less(last, start) {
+                last -= 1;
+            }
+        }
+        let pivot = first - 1;
+        if pivot != start {
+            array.swap(start, pivot);
+        }
+        (pivot, already_partitioned)
+    }
+    // Partitions around the pivot at `start`, with values equal to it going left
+    fn partition_left(array: &mut SortRecorder, start: usize, end: usize) -> usize {
+        array.pivot(start);
+        let mut last = end - 1;
+        while last > start && array.less(start, last) {
+            last -= 1;
+        }
+        let mut first = start + 1;
+        while first <= last && !array.less(start, first) {
+            first += 1;
+        }
+
+        while first < last {
+            array.swap(first, last);
+            last -= 1;
+            while array.less(start, last) {
+                last -= 1;
+            }
+            first += 1;
+            while !array.less(start, first) {
+                first += 1;
+            }
+        }
+        if last != start {
+            array.swap(start, last);
+        }
+        last
+    }
+    // Swaps a few values a quarter of the way in from each end, so inputs built to defeat
+    // the pivot choice stop doing so
+    fn break_patterns(array: &mut SortRecorder, start: usize, end: usize) {
+        let len = end - start;
+        if len < INSERTION_THRESHOLD {
+            return;
+        }
+        let quarter = len / 4;
+        array.swap(start, start + quarter);
+        array.swap(end - 1, end - quarter);
+        if len > NINTHER_THRESHOLD {
+            array.swap(start + 1, start + quarter + 1);
+            array.swap(start + 2, start + quarter + 2);
+            array.swap(end - 2, end - quarter - 1);
+            array.swap(end - 3, end - quarter - 2);
+        }
+    }
+    // Insertion sorts `start..end` unless that takes more than a few moves. Returns whether
+    // the range ended up sorted.
+    fn partial_insertion_sort(array: &mut SortRecorder, start: usize, end: usize) -> bool {
+        let mut moved = 0;
+        for i in start + 1..end {
+            if moved > PARTIAL_INSERTION_LIMIT {
+                return false;
+            }
+            let key = array[i];
+            let mut hole = i;
+            while hole > start
+                && array.compare_values(hole - 1, array[hole - 1], hole, key) == Ordering::Greater
+            {
+                array.write(hole, array[hole - 1]);
+                hole -= 1;
+            }
+            if hole != i {
+                array.write(hole, key);
+            }
+            moved += i - hole;
+        }
+        true
+    }
+}
@@ -0,0 +1,211 @@
+use crate::sorting_algorithms::{Heap, HeapError};
+use std::fmt;
+
+/// One priority queue operation on a `Heap`, as typed in a script or at the prompt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HeapOp {
+    Build(Vec<i32>), // build 5 3 8: replace the contents and heapify
+    Insert(i32),     // insert 7
+    Extract,         // extract: remove the root
+    Peek,            // peek: look at the root
+    IncreaseKey { index: usize, key: i32 }, // increase 2 9: raise the key at index 2 to 9
+    Delete(usize),   // delete 1: remove the value at index 1
+}
+
+impl HeapOp {
+    /// Usage line for every operation.
+    pub const SYNTAX: &'static str =
+        "build <VALUES>, insert <N>, extract, peek, increase <INDEX> <KEY>, delete <INDEX>";
+
+    /// Parses one operation such as `insert 7`, or returns None when it is not one.
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut words = text
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|word| !word.is_empty());
+        let name = words.next()?.to_ascii_lowercase();
+        let args = words
+            .map(|word| word.parse::<i64>().ok())
+            .collect::<Option<Vec<i64>>>()?;
+        let value = |arg: i64| i32::try_from(arg).ok();
+        let index = |arg: i64| usize::try_from(arg).ok();
+        match (name.as_str(), args.as_slice()) {
+            ("build", args) => Some(Self::Build(
+                args.iter().map(|&arg| value(arg)).collect::<Option<_>>()?,
+            )),
+            ("insert", &[arg]) => Some(Self::Insert(value(arg)?)),
+            ("extract", []) => Some(Self::Extract),
+            ("peek", []) => Some(Self::Peek),
+            ("increase", &[idx, key]) => Some(Self::IncreaseKey {
+                index: index(idx)?,
+                key: value(key)?,
+            }),
+            ("delete", &[idx]) => Some(Self::Delete(index(idx)?)),
+            _ => None,
+        }
+    }
+
+    /// Parses operations separated by semicolons or new lines. Returns the first part that
+    /// is not an operation as the error.
+    pub fn parse_script(script: &str) -> Result<Vec<Self>, String> {
+        script
+            .split([';', '\n'])
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+            .map(|part| Self::parse(part).ok_or_else(|| part.to_string()))
+            .collect()
+    }
+
+    /// Performs the operation on `heap`, returning a line describing the outcome.
+    pub fn apply(&self, heap: &mut Heap) -> Result<String, HeapError> {
+        let outcome = match *self {
+            Self::Build(ref values) => {
+                heap.build_heap(values);
+                format!("built a {} of {} values", heap.kind.name(), values.len())
+            }
+            Self::Insert(value) => {
+                heap.insert(value);
+                format!("inserted {}", value)
+            }
+            Self::Extract => match heap.extract_top() {
+                Some(top) => format!("extracted {}", top),
+                None => "the heap is empty".to_string(),
+            },
+            Self::Peek => match heap.peek() {
+                Some(top) => format!("top is {}", top),
+                None => "the heap is empty".to_string(),
+            },
+            Self::IncreaseKey { index, key } => {
+                heap.increase_key(index, key)?;
+                format!("increased the key at index {} to {}", index, key)
+            }
+            Self::Delete(index) => format!("deleted {}", heap.delete(index)?),
+        };
+        Ok(outcome)
+    }
+}
+
+impl fmt::Display for HeapOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Build(values) => {
+                let values: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+                write!(f, "build {}", values.join(" "))
+            }
+            Self::Insert(value) => write!(f, "insert {}", value),
+            Self::Extract => write!(f, "extract"),
+            Self::Peek => write!(f, "peek"),
+            Self::IncreaseKey { index, key } => write!(f, "increase {} {}", index, key),
+            Self::Delete(index) => write!(f, "delete {}", index),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sorting_algorithms::{HeapError, HeapKind};
+
+    // The root must outrank both children everywhere
+    fn is_heap(values: &[i32], kind: HeapKind) -> bool {
+        (1..values.len()).all(|idx| {
+            let parent = values[(idx - 1) / 2];
+            match kind {
+                HeapKind::Max => parent >= values[idx],
+                HeapKind::Min => parent <= values[idx],
+            }
+        })
+    }
+
+    #[test]
+    fn scripted_operations_keep_the_heap_property() {
+        let script = "build 5 3 8 1 9 2; insert 7; extract; increase 4 20; delete 1; peek";
+        let ops = HeapOp::parse_script(script).unwrap();
+        assert_eq!(ops.len(), 6);
+        for kind in [HeapKind::Max, HeapKind::Min] {
+            let mut heap = Heap::with_kind(kind);
+            for op in ops.iter() {
+                let outcome = op.apply(&mut heap);
+                // 20 ranks above every key in a max-heap and below them in a min-heap
+                match (op, kind) {
+                    (HeapOp::IncreaseKey { .. }, HeapKind::Min) => assert!(
+                        matches!(
+                            outcome,
+                            Err(HeapError::KeyNotIncreased {
+                                index: 4,
+                                key: 20,
+                                ..
+                            })
+                        ),
+                        "{:?}",
+                        outcome
+                    ),
+                    _ => assert!(
+                        outcome.is_ok(),
+                        "{} after {}: {:?}",
+                        kind.name(),
+                        op,
+                        outcome
+                    ),
+                }
+                assert!(
+                    is_heap(heap.nodes.values(), kind),
+                    "{} after {}",
+                    kind.name(),
+                    op
+                );
+                let trace = heap.take_trace();
+                let mut replayed = trace.initial.clone();
+                trace.replay(|step, _| replayed = step.to_vec());
+                assert_eq!(replayed, heap.nodes.values());
+            }
+        }
+        assert_eq!(
+            HeapOp::parse_script("insert x"),
+            Err("insert x".to_string())
+        );
+    }
+
+    #[test]
+    fn increase_key_moves_the_key_toward_the_root() {
+        // Built from 5 3 8 1 9 2, the max-heap is 9 5 8 1 3 2 and the min-heap 1 3 2 5 9 8
+        // Each case raises index 4 to `key`, after first trying `lower`, which ranks below it
+        let cases = [
+            (HeapKind::Max, 7, [9, 7, 8, 1, 5, 2], 1),
+            (HeapKind::Max, 20, [20, 9, 8, 1, 5, 2], 2),
+            (HeapKind::Min, 2, [1, 2, 2, 5, 3, 8], 10),
+            (HeapKind::Min, -20, [-20, 1, 2, 5, 3, 8], 11),
+        ];
+        for (kind, key, expected, lower) in cases {
+            let mut heap = Heap::with_kind(kind);
+            HeapOp::Build(vec![5, 3, 8, 1, 9, 2])
+                .apply(&mut heap)
+                .unwrap();
+            let current = heap.nodes[4];
+            // A key that ranks lower is refused and leaves the heap as it was
+            let before = heap.nodes.values().to_vec();
+            assert_eq!(
+                heap.increase_key(4, lower),
+                Err(HeapError::KeyNotIncreased {
+                    index: 4,
+                    current,
+                    key: lower
+                })
+            );
+            assert_eq!(
+                heap.increase_key(6, key),
+                Err(HeapError::OutOfRange { index: 6, len: 6 })
+            );
+            assert_eq!(heap.nodes.values(), before);
+
+            assert_eq!(
+                heap.increase_key(4, key),
+                Ok(()),
+                "{} to {}",
+                kind.name(),
+                key
+            );
+            assert_eq!(heap.nodes.values(), expected, "{} to {}", kind.name(), key);
+            assert!(is_heap(heap.nodes.values(), kind));
+        }
+    }
+}
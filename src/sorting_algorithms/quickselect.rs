@@ -0,0 +1,140 @@
+use crate::sorting_algorithms::{
+    Complexity, MedianOfMedians, PartitionScheme, PivotRule, QuickSort, Selector, SortInfo,
+    SortRecorder, Sorter,
+};
+
+/// Hoare's quickselect. Each partition fixes the pivot in its sorted place, and only the side
+/// holding the wanted rank is partitioned again, until the pivot lands on it.
+pub struct QuickSelect {
+    quicksort: QuickSort, // Picks pivots and partitions, highlighted as in quicksort
+}
+
+impl Default for QuickSelect {
+    fn default() -> Self {
+        Self::new(PivotRule::Last)
+    }
+}
+
+impl Selector for QuickSelect {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "quickselect",
+            title: "Quickselect Algorithm",
+            stable: false,
+            in_place: true,
+            complexity: Complexity {
+                best: "O(n)",
+                average: "O(n)",
+                worst: "O(n^2)",
+                space: "O(1)",
+            },
+        }
+    }
+
+    fn title(&self) -> String {
+        format!(
+            "{} ({} pivot)",
+            self.info().title,
+            self.quicksort.pivot.name()
+        )
+    }
+
+    fn reseed(&mut self, seed: u64) {
+        self.quicksort.reseed(seed);
+    }
+
+    fn select(&mut self, array: &mut SortRecorder, k: usize) {
+        select_with_budget(&mut self.quicksort, array, k, usize::MAX);
+    }
+}
+
+impl QuickSelect {
+    pub fn new(pivot: PivotRule) -> Self {
+        QuickSelect {
+            quicksort: QuickSort::with_options(pivot, PartitionScheme::Lomuto),
+        }
+    }
+}
+
+/// Quickselect that switches to median-of-medians pivots once it has partitioned more than
+/// twice the depth of a balanced split without reaching the wanted rank, which keeps the worst
+/// case linear.
+pub struct IntroSelect {
+    quicksort: QuickSort,
+}
+
+impl Default for IntroSelect {
+    fn default() -> Self {
+        Self::new(PivotRule::Last)
+    }
+}
+
+impl Selector for IntroSelect {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "introselect",
+            title: "Introselect Algorithm",
+            stable: false,
+            in_place: true,
+            complexity: Complexity {
+                best: "O(n)",
+                average: "O(n)",
+                worst: "O(n)",
+                space: "O(log n)",
+            },
+        }
+    }
+
+    fn title(&self) -> String {
+        format!(
+            "{} ({} pivot)",
+            self.info().title,
+            self.quicksort.pivot.name()
+        )
+    }
+
+    fn reseed(&mut self, seed: u64) {
+        self.quicksort.reseed(seed);
+    }
+
+    fn select(&mut self, array: &mut SortRecorder, k: usize) {
+        let budget = 2 * array.len().max(1).ilog2() as usize;
+        select_with_budget(&mut self.quicksort, array, k, budget);
+    }
+}
+
+impl IntroSelect {
+    pub fn new(pivot: PivotRule) -> Self {
+        IntroSelect {
+            quicksort: QuickSort::with_options(pivot, PartitionScheme::Lomuto),
+        }
+    }
+}
+
+// Partitions around pivots picked by `quicksort` until the pivot lands on `k`, handing the
+// remaining range to median of medians after `budget` partitions
+fn select_with_budget(
+    quicksort: &mut QuickSort,
+    array: &mut SortRecorder,
+    k: usize,
+    mut budget: usize,
+) {
+    let (mut low, mut high) = (0, array.len() as i32 - 1);
+    let k = k as i32;
+    while low < high {
+        if budget == 0 {
+            MedianOfMedians::select_range(array, low, high, k);
+            break;
+        }
+        budget -= 1;
+        let pivot = quicksort.partition(array, low, high);
+        if pivot == k {
+            break;
+        } else if k < pivot {
+            high = pivot - 1;
+        } else {
+            low = pivot + 1;
+        }
+    }
+    array.selected(k as usize);
+}
@@ -1,72 +1,375 @@
-use crate::sorting_algorithms::SortGraph;
+use crate::sorting_algorithms::{Complexity, SortInfo, SortRecorder, Sorter};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::cmp::Ordering;
 
-pub struct QuickSort<'a, 'b, 'c> {
-    pub graph: &'a mut SortGraph<'b, 'c>,
-    pub values: Vec<i32>,
+// Ranges shorter than this take a median of three instead of a ninther
+const NINTHER_THRESHOLD: i32 = 9;
+
+/// How a `QuickSort` picks the pivot of each range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PivotRule {
+    First,         // First value of the range
+    Last,          // Last value of the range
+    Middle,        // Value in the middle of the range
+    Random,        // Value at a random position
+    MedianOfThree, // Median of the first, middle and last values
+    Ninther,       // Median of the medians of three spread out triples, after Tukey
 }
 
-impl<'a, 'b, 'c> QuickSort<'a, 'b, 'c> {
-    pub fn new(graph: &'a mut SortGraph<'b, 'c>) -> Self {
-        let values = graph.values.clone();
-        QuickSort {
-            graph,
-            values: values.to_vec(),
+impl PivotRule {
+    pub const ALL: [PivotRule; 6] = [
+        Self::First,
+        Self::Last,
+        Self::Middle,
+        Self::Random,
+        Self::MedianOfThree,
+        Self::Ninther,
+    ];
+
+    /// Registry key used to pick the rule at runtime.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::First => "first",
+            Self::Last => "last",
+            Self::Middle => "middle",
+            Self::Random => "random",
+            Self::MedianOfThree => "median-of-three",
+            Self::Ninther => "ninther",
+        }
+    }
+
+    /// Looks up a rule by its registry name, ignoring case.
+    pub fn find(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|rule| rule.name().eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+/// How a `QuickSort` splits a range around its pivot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartitionScheme {
+    Lomuto,    // One scan from the left, pivot kept at the end
+    Hoare,     // Two scans meeting in the middle, swapping out of place pairs
+    ThreeWay,  // Dijkstra's Dutch national flag, values equal to the pivot in the middle
+    DualPivot, // Yaroslavskiy's two pivots and three parts, as in Java's sort
+}
+
+impl PartitionScheme {
+    pub const ALL: [PartitionScheme; 4] =
+        [Self::Lomuto, Self::Hoare, Self::ThreeWay, Self::DualPivot];
+
+    /// Registry key used to pick the scheme at runtime.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Lomuto => "lomuto",
+            Self::Hoare => "hoare",
+            Self::ThreeWay => "three-way",
+            Self::DualPivot => "dual-pivot",
+        }
+    }
+
+    /// Looks up a scheme by its registry name, ignoring case.
+    pub fn find(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|scheme| scheme.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    fn title(&self) -> &'static str {
+        match self {
+            Self::Lomuto => "Lomuto",
+            Self::Hoare => "Hoare",
+            Self::ThreeWay => "three-way",
+            Self::DualPivot => "dual-pivot",
         }
     }
-    pub fn sort(&mut self) {
-        self.graph.display_graph();
-        self.quick_sort_helper(0, self.values.len() as i32 - 1);
-        self.graph.display_graph();
+}
+
+pub struct QuickSort {
+    pub pivot: PivotRule,
+    pub scheme: PartitionScheme,
+    rng: StdRng, // Drives `PivotRule::Random`
+}
+
+impl Default for QuickSort {
+    fn default() -> Self {
+        Self::with_options(PivotRule::Last, PartitionScheme::Lomuto)
+    }
+}
+
+impl Sorter for QuickSort {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "quicksort",
+            title: "Quick Sort Algorithm",
+            stable: false,
+            in_place: true,
+            complexity: Complexity {
+                best: "O(n log n)",
+                average: "O(n log n)",
+                worst: "O(n^2)",
+                space: "O(log n)",
+            },
+        }
+    }
+
+    fn title(&self) -> String {
+        format!(
+            "{} ({} pivot, {} partition)",
+            self.info().title,
+            self.pivot.name(),
+            self.scheme.title()
+        )
+    }
+
+    fn reseed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    fn sort(&mut self, array: &mut SortRecorder) {
+        self.quick_sort_helper(array, 0, array.len() as i32 - 1);
+    }
+}
+
+impl QuickSort {
+    pub fn new() -> Self {
+        Self::default()
     }
-    pub fn values(&self) -> &[i32] {
-        &self.values
+    pub fn with_options(pivot: PivotRule, scheme: PartitionScheme) -> Self {
+        QuickSort {
+            pivot,
+            scheme,
+            rng: StdRng::seed_from_u64(0),
+        }
     }
-    pub fn quick_sort_helper(&mut self, low: i32, high: i32) {
-        if low < high {
-            let pi = self.partition(low, high);
-            self.quick_sort_helper(low, pi - 1);
-            self.quick_sort_helper(pi + 1, high);
+    pub fn quick_sort_helper(&mut self, array: &mut SortRecorder, low: i32, high: i32) {
+        if low >= high {
+            return;
+        }
+        match self.scheme {
+            PartitionScheme::Lomuto => {
+                let pi = self.partition(array, low, high);
+                self.quick_sort_helper(array, low, pi - 1);
+                self.quick_sort_helper(array, pi + 1, high);
+            }
+            PartitionScheme::Hoare => {
+                let split = self.hoare_partition(array, low, high);
+                self.quick_sort_helper(array, low, split);
+                self.quick_sort_helper(array, split + 1, high);
+            }
+            PartitionScheme::ThreeWay => {
+                let (lt, gt) = self.three_way_partition(array, low, high);
+                self.quick_sort_helper(array, low, lt - 1);
+                self.quick_sort_helper(array, gt + 1, high);
+            }
+            PartitionScheme::DualPivot => {
+                let (lp, rp) = self.dual_pivot_partition(array, low, high);
+                self.quick_sort_helper(array, low, lp - 1);
+                self.quick_sort_helper(array, lp + 1, rp - 1);
+                self.quick_sort_helper(array, rp + 1, high);
+            }
         }
     }
     /// Partition the vector and return the index of the pivot.
-    fn partition(&mut self, low: i32, high: i32) -> i32 {
-        let pivot = self.values[high as usize];
+    pub fn partition(&mut self, array: &mut SortRecorder, low: i32, high: i32) -> i32 {
+        array.focus(low as usize, high as usize + 1);
+        let chosen = self.choose_pivot(array, low, high);
+        Self::partition_around(array, low, high, chosen)
+    }
+    /// Partition `low..=high` around the value at `chosen`, picked by the caller, and return
+    /// the index the pivot ends up at.
+    pub fn partition_around(array: &mut SortRecorder, low: i32, high: i32, chosen: i32) -> i32 {
+        array.phase("partition");
+        if chosen != high {
+            array.swap(chosen as usize, high as usize);
+        }
+        array.pivot(high as usize);
         let mut i = low - 1;
         for j in low..high {
-            if self.values[j as usize] < pivot {
+            if array.less(j as usize, high as usize) {
                 i += 1;
-                self.graph.display_graph_with_highlights(
-                    high as usize,
-                    low,
-                    high,
-                    (i as usize, j as usize),
-                );
-                self.values.swap(i as usize, j as usize);
-                self.graph.values.swap(i as usize, j as usize);
-                self.graph.display_graph_with_highlights(
-                    high as usize,
-                    low,
-                    high,
-                    (i as usize, j as usize),
-                );
+                array.swap(i as usize, j as usize);
             }
         }
-        self.values.swap((i + 1) as usize, high as usize);
-        self.graph.values = self.values.clone();
-        self.graph.display_graph_with_highlights(
-            high as usize,
-            low,
-            high,
-            ((i + 1) as usize, high as usize),
-        );
-        self.graph.values.swap((i + 1) as usize, high as usize);
-        self.graph.display_graph_with_highlights(
-            high as usize,
-            low,
-            high,
-            ((i + 1) as usize, high as usize),
-        );
+        array.swap((i + 1) as usize, high as usize);
 
         i + 1
     }
+    /// Partition around the pivot value with two scans closing in from both ends. Returns
+    /// the last index of the left part, which holds no value greater than the pivot.
+    fn hoare_partition(&mut self, array: &mut SortRecorder, low: i32, high: i32) -> i32 {
+        array.focus(low as usize, high as usize + 1);
+        // The pivot goes first, so the right scan always stops before `high` and both
+        // parts are left non-empty
+        let chosen = self.choose_pivot(array, low, high);
+        array.phase("partition");
+        if chosen != low {
+            array.swap(chosen as usize, low as usize);
+        }
+        array.pivot(low as usize);
+        let pivot = array[low as usize];
+        let (mut i, mut j) = (low - 1, high + 1);
+        loop {
+            i += 1;
+            while array.compare_values(i as usize, array[i as usize], low as usize, pivot)
+                == Ordering::Less
+            {
+                i += 1;
+            }
+            j -= 1;
+            while array.compare_values(j as usize, array[j as usize], low as usize, pivot)
+                == Ordering::Greater
+            {
+                j -= 1;
+            }
+            if i >= j {
+                return j;
+            }
+            array.swap(i as usize, j as usize);
+        }
+    }
+    /// Partition into values less than, equal to and greater than the pivot. Returns the
+    /// first and last index of the equal values, which are already in place.
+    fn three_way_partition(&mut self, array: &mut SortRecorder, low: i32, high: i32) -> (i32, i32) {
+        array.focus(low as usize, high as usize + 1);
+        let chosen = self.choose_pivot(array, low, high);
+        Self::three_way_partition_around(array, low, high, chosen)
+    }
+    /// Three-way partition of `low..=high` around the value at `chosen`, picked by the caller.
+    /// Returns the first and last index of the values equal to it.
+    pub fn three_way_partition_around(
+        array: &mut SortRecorder,
+        low: i32,
+        high: i32,
+        chosen: i32,
+    ) -> (i32, i32) {
+        array.phase("partition");
+        if chosen != low {
+            array.swap(chosen as usize, low as usize);
+        }
+        array.pivot(low as usize);
+        // low..lt is less than the pivot, lt..i equal to it and gt+1..=high greater, with
+        // `lt` always holding a value equal to the pivot
+        let (mut lt, mut i, mut gt) = (low, low + 1, high);
+        while i <= gt {
+            match array.compare(i as usize, lt as usize) {
+                Ordering::Less => {
+                    array.swap(lt as usize, i as usize);
+                    lt += 1;
+                    i += 1;
+                }
+                Ordering::Greater => {
+                    array.swap(i as usize, gt as usize);
+                    gt -= 1;
+                }
+                Ordering::Equal => i += 1,
+            }
+        }
+        (lt, gt)
+    }
+    /// Partition into values less than the smaller pivot, values between the pivots and values
+    /// greater than the larger pivot. Returns where the two pivots end up.
+    fn dual_pivot_partition(
+        &mut self,
+        array: &mut SortRecorder,
+        low: i32,
+        high: i32,
+    ) -> (i32, i32) {
+        array.focus(low as usize, high as usize + 1);
+        // Each pivot is picked by the pivot rule from its own half of the range
+        let middle = low + (high - low) / 2;
+        let left = self.choose_pivot(array, low, middle);
+        let right = self.choose_pivot(array, middle + 1, high);
+        array.phase("partition");
+        if left != low {
+            array.swap(left as usize, low as usize);
+        }
+        if right != high {
+            array.swap(right as usize, high as usize);
+        }
+        if array.less(high as usize, low as usize) {
+            array.swap(low as usize, high as usize);
+        }
+        array.pivot(low as usize);
+        array.pivot(high as usize);
+
+        // low+1..l is less than the left pivot, l..k between the pivots and g+1..high
+        // greater than the right pivot
+        let (mut l, mut k, mut g) = (low + 1, low + 1, high - 1);
+        while k <= g {
+            if array.less(k as usize, low as usize) {
+                array.swap(k as usize, l as usize);
+                l += 1;
+            } else if !array.less(k as usize, high as usize) {
+                while k < g && array.less(high as usize, g as usize) {
+                    g -= 1;
+                }
+                array.swap(k as usize, g as usize);
+                g -= 1;
+                if array.less(k as usize, low as usize) {
+                    array.swap(k as usize, l as usize);
+                    l += 1;
+                }
+            }
+            k += 1;
+        }
+        l -= 1;
+        g += 1;
+        array.swap(low as usize, l as usize);
+        array.swap(high as usize, g as usize);
+        (l, g)
+    }
+    /// Pick a pivot in `low..=high` by the pivot rule and return its index, leaving the
+    /// values where they are.
+    fn choose_pivot(&mut self, array: &mut SortRecorder, low: i32, high: i32) -> i32 {
+        array.phase(match self.pivot {
+            PivotRule::First => "first pivot",
+            PivotRule::Last => "last pivot",
+            PivotRule::Middle => "middle pivot",
+            PivotRule::Random => "random pivot",
+            PivotRule::MedianOfThree => "median-of-three pivot",
+            PivotRule::Ninther => "ninther pivot",
+        });
+        let middle = low + (high - low) / 2;
+        match self.pivot {
+            PivotRule::First => low,
+            PivotRule::Last => high,
+            PivotRule::Middle => middle,
+            PivotRule::Random => self.rng.gen_range(low..=high),
+            PivotRule::MedianOfThree => Self::median_of_three(array, low, middle, high),
+            PivotRule::Ninther if high - low + 1 < NINTHER_THRESHOLD => {
+                Self::median_of_three(array, low, middle, high)
+            }
+            PivotRule::Ninther => {
+                let step = (high - low + 1) / 8;
+                let medians = [
+                    Self::median_of_three(array, low, low + step, low + 2 * step),
+                    Self::median_of_three(array, middle - step, middle, middle + step),
+                    Self::median_of_three(array, high - 2 * step, high - step, high),
+                ];
+                Self::median_of_three(array, medians[0], medians[1], medians[2])
+            }
+        }
+    }
+    // Index of the median of the values at `a`, `b` and `c`
+    fn median_of_three(array: &mut SortRecorder, a: i32, b: i32, c: i32) -> i32 {
+        let (a, b, c) = (a as usize, b as usize, c as usize);
+        let median = if array.less(a, b) {
+            if array.less(b, c) {
+                b
+            } else if array.less(a, c) {
+                c
+            } else {
+                a
+            }
+        } else if array.less(a, c) {
+            a
+        } else if array.less(b, c) {
+            c
+        } else {
+            b
+        };
+        median as i32
+    }
 }
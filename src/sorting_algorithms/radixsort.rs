@@ -0,0 +1,127 @@
+use crate::sorting_algorithms::{Complexity, CountingSort, SortInfo, SortRecorder, Sorter};
+
+/// Radix sort from the least significant digit up, one stable counting pass per digit.
+pub struct LsdRadixSort {
+    pub base: u32,
+}
+
+/// Radix sort from the most significant digit down, sorting every bucket on the next digit.
+pub struct MsdRadixSort {
+    pub base: u32,
+}
+
+impl Default for LsdRadixSort {
+    fn default() -> Self {
+        Self::new(10)
+    }
+}
+
+impl Default for MsdRadixSort {
+    fn default() -> Self {
+        Self::new(10)
+    }
+}
+
+impl Sorter for LsdRadixSort {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "lsdradixsort",
+            title: "LSD Radix Sort Algorithm",
+            stable: true,
+            in_place: false,
+            complexity: Complexity {
+                best: "O(d(n + b))",
+                average: "O(d(n + b))",
+                worst: "O(d(n + b))",
+                space: "O(n + b)",
+            },
+        }
+    }
+
+    fn sort(&mut self, array: &mut SortRecorder) {
+        let Some((min, max)) = CountingSort::key_range(array, 0, array.len()) else {
+            return;
+        };
+        let (base, len) = (self.base as i64, array.len());
+        let mut place = 1;
+        while (max - min) / place > 0 {
+            CountingSort::counting_pass(array, 0, len, base as usize, |key| {
+                digit(key - min, place, base)
+            });
+            place = match place.checked_mul(base) {
+                Some(place) => place,
+                None => break,
+            };
+        }
+        array.sorted(0, len);
+    }
+}
+
+impl Sorter for MsdRadixSort {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "msdradixsort",
+            title: "MSD Radix Sort Algorithm",
+            stable: true,
+            in_place: false,
+            complexity: Complexity {
+                best: "O(n + b)",
+                average: "O(d(n + b))",
+                worst: "O(d(n + b))",
+                space: "O(n + db)",
+            },
+        }
+    }
+
+    fn sort(&mut self, array: &mut SortRecorder) {
+        let Some((min, max)) = CountingSort::key_range(array, 0, array.len()) else {
+            return;
+        };
+        // Start from the highest place any key has a digit in
+        let base = self.base as i64;
+        let mut place = 1;
+        while (max - min) / place >= base {
+            place *= base;
+        }
+        let len = array.len();
+        self.sort_bucket(array, 0, len, min, place);
+        array.sorted(0, len);
+    }
+}
+
+impl LsdRadixSort {
+    pub fn new(base: u32) -> Self {
+        LsdRadixSort { base: base.max(2) }
+    }
+}
+
+impl MsdRadixSort {
+    pub fn new(base: u32) -> Self {
+        MsdRadixSort { base: base.max(2) }
+    }
+    fn sort_bucket(
+        &mut self,
+        array: &mut SortRecorder,
+        start: usize,
+        end: usize,
+        min: i64,
+        place: i64,
+    ) {
+        if end - start <= 1 || place == 0 {
+            return;
+        }
+        let base = self.base as i64;
+        array.focus(start, end);
+        let bounds = CountingSort::counting_pass(array, start, end, base as usize, |key| {
+            digit(key - min, place, base)
+        });
+        for bucket in bounds.windows(2) {
+            self.sort_bucket(array, bucket[0], bucket[1], min, place / base);
+        }
+    }
+}
+
+// The digit of `value` at `place`, a power of `base`
+fn digit(value: i64, place: i64, base: i64) -> usize {
+    (value / place % base) as usize
+}
@@ -0,0 +1,162 @@
+use crate::sorting_algorithms::{
+    BinarySearch, Complexity, ExponentialSearch, InterpolationSearch, JumpSearch, SortEvent,
+    SortRecorder, SortTrace, TernarySearch,
+};
+use std::{fmt, time::Instant};
+
+/// Static description of a search algorithm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchInfo {
+    pub name: &'static str,  // Registry key used to pick the algorithm at runtime
+    pub title: &'static str, // Title shown above the graph
+    pub complexity: Complexity,
+}
+
+/// Common interface implemented by every search algorithm, which looks for a value in an
+/// array that is already sorted.
+pub trait Searcher {
+    fn info(&self) -> SearchInfo;
+
+    fn name(&self) -> &'static str {
+        self.info().name
+    }
+
+    fn title(&self) -> String {
+        self.info().title.to_string()
+    }
+
+    /// Returns an index of the sorted `array` holding `target`, comparing values with it
+    /// through `SortRecorder::probe` and narrowing the range it can still be in with
+    /// `SortRecorder::focus`.
+    fn search(&mut self, array: &mut SortRecorder, target: i32) -> Option<usize>;
+
+    /// Runs on a copy of the sorted `values` and returns the recorded run, timed without any
+    /// playback. The run starts with the target and ends marking where it was found.
+    fn record(&mut self, values: &[i32], target: i32) -> SortTrace {
+        let mut array = SortRecorder::new(values.to_vec());
+        array.target(target);
+        let start = Instant::now();
+        let found = self.search(&mut array, target);
+        let elapsed = start.elapsed();
+        if let Some(index) = found {
+            array.found(index);
+        }
+        let mut trace = array.into_trace();
+        trace.elapsed = elapsed;
+        trace
+    }
+}
+
+/// Returns one instance of every available search algorithm.
+pub fn searchers() -> Vec<Box<dyn Searcher>> {
+    vec![
+        Box::new(BinarySearch::new()),
+        Box::new(InterpolationSearch::new()),
+        Box::new(ExponentialSearch::new()),
+        Box::new(JumpSearch::new()),
+        Box::new(TernarySearch::new()),
+    ]
+}
+
+/// Registry keys of every search algorithm, for listings and error messages.
+pub fn searcher_names() -> Vec<&'static str> {
+    searchers().iter().map(|searcher| searcher.name()).collect()
+}
+
+/// Looks up a search algorithm by its registry name, ignoring case.
+pub fn find_searcher(name: &str) -> Option<Box<dyn Searcher>> {
+    searchers()
+        .into_iter()
+        .find(|searcher| searcher.name().eq_ignore_ascii_case(name.trim()))
+}
+
+/// Result of checking a search run against the values it searched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchCheck {
+    pub target: i32,
+    pub found: Option<usize>, // Index the search returned
+    pub correct: bool,        // The index holds the target, or no index does
+}
+
+impl SearchCheck {
+    /// Checks the index `trace` ended on against the values it searched for `target`.
+    pub fn check(trace: &SortTrace, target: i32) -> Self {
+        let found = trace.events.iter().rev().find_map(|event| match *event {
+            SortEvent::Found(index) => Some(index),
+            _ => None,
+        });
+        let correct = match found {
+            Some(index) => trace.initial.get(index) == Some(&target),
+            None => !trace.initial.contains(&target),
+        };
+        Self {
+            target,
+            found,
+            correct,
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.correct
+    }
+}
+
+impl fmt::Display for SearchCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.found, self.correct) {
+            (Some(index), true) => write!(f, "verified {} at index {}", self.target, index),
+            (None, true) => write!(f, "verified {} is not there", self.target),
+            (Some(index), false) => {
+                write!(f, "FAILED, index {} does not hold {}", index, self.target)
+            }
+            (None, false) => write!(f, "FAILED, missed {}", self.target),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sorting_algorithms::Distribution;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn searchers_find_every_value() {
+        let mut rng = StdRng::seed_from_u64(5);
+        for distribution in Distribution::ALL {
+            for len in [0, 1, 2, rng.gen_range(3..=60)] {
+                let mut values = distribution.generate(&mut rng, len, -20, 20);
+                values.sort_unstable();
+                for searcher in searchers().iter_mut() {
+                    for target in -22..=22 {
+                        let trace = searcher.record(&values, target);
+                        let check = SearchCheck::check(&trace, target);
+                        assert!(
+                            check.passed(),
+                            "{} looking for {} in {:?}: {}",
+                            searcher.name(),
+                            target,
+                            values,
+                            check
+                        );
+                        // Every probe lands inside the range shown as still searched
+                        let mut window = (0, len);
+                        for event in trace.events.iter() {
+                            match *event {
+                                SortEvent::Focus { start, end } => window = (start, end),
+                                SortEvent::Probe(index) => assert!(
+                                    index >= window.0 && index < window.1,
+                                    "{} probed {} outside {:?}",
+                                    searcher.name(),
+                                    index,
+                                    window
+                                ),
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
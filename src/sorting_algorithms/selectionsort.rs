@@ -0,0 +1,47 @@
+use crate::sorting_algorithms::{Complexity, SortInfo, SortRecorder, Sorter};
+
+#[derive(Default)]
+pub struct SelectionSort;
+
+impl Sorter for SelectionSort {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "selectionsort",
+            title: "Selection Sort Algorithm",
+            stable: false,
+            in_place: true,
+            complexity: Complexity {
+                best: "O(n^2)",
+                average: "O(n^2)",
+                worst: "O(n^2)",
+                space: "O(1)",
+            },
+        }
+    }
+
+    fn sort(&mut self, array: &mut SortRecorder) {
+        let len = array.len();
+        for i in 0..len.saturating_sub(1) {
+            array.sorted(0, i);
+            // Find the smallest value left and swap it to the end of the sorted prefix
+            let mut min = i;
+            array.candidate(min);
+            for j in i + 1..len {
+                if array.less(j, min) {
+                    min = j;
+                    array.candidate(min);
+                }
+            }
+            if min != i {
+                array.swap(i, min);
+            }
+        }
+        array.sorted(0, len);
+    }
+}
+
+impl SelectionSort {
+    pub fn new() -> Self {
+        SelectionSort
+    }
+}
@@ -0,0 +1,180 @@
+use crate::sorting_algorithms::{
+    IntroSelect, MedianOfMedians, PartialSort, QuickSelect, SortInfo, SortRecorder, SortTrace,
+    SorterOptions,
+};
+use std::{fmt, time::Instant};
+
+/// Common interface implemented by every selection algorithm, which finds one order statistic
+/// instead of sorting the whole array.
+pub trait Selector {
+    fn info(&self) -> SortInfo;
+
+    fn name(&self) -> &'static str {
+        self.info().name
+    }
+
+    /// Title shown above the graph, naming the chosen variant for selectors that have options.
+    fn title(&self) -> String {
+        self.info().title.to_string()
+    }
+
+    /// Reseeds any randomness the algorithm uses, so seeded runs replay exactly.
+    fn reseed(&mut self, _seed: u64) {}
+
+    /// Moves the value of rank `k`, counted from 0, to index `k`, with no larger value before
+    /// it and no smaller one after it, and marks it with `SortRecorder::selected` as soon as
+    /// it is in place.
+    fn select(&mut self, array: &mut SortRecorder, k: usize);
+
+    /// Runs on a copy of `values` and returns the recorded run, timed without any playback.
+    fn record(&mut self, values: &[i32], k: usize) -> SortTrace {
+        let mut array = SortRecorder::new(values.to_vec());
+        let start = Instant::now();
+        self.select(&mut array, k);
+        let elapsed = start.elapsed();
+        let mut trace = array.into_trace();
+        trace.elapsed = elapsed;
+        trace
+    }
+}
+
+/// Returns one instance of every available selection algorithm, with the default variants.
+pub fn selectors() -> Vec<Box<dyn Selector>> {
+    selectors_with(&SorterOptions::default())
+}
+
+/// Returns one instance of every available selection algorithm, built with `options`.
+pub fn selectors_with(options: &SorterOptions) -> Vec<Box<dyn Selector>> {
+    vec![
+        Box::new(QuickSelect::new(options.pivot)),
+        Box::new(IntroSelect::new(options.pivot)),
+        Box::new(MedianOfMedians::new()),
+        Box::new(PartialSort::new()),
+    ]
+}
+
+/// Registry keys of every selection algorithm, for listings and error messages.
+pub fn selector_names() -> Vec<&'static str> {
+    selectors().iter().map(|selector| selector.name()).collect()
+}
+
+/// Looks up a selection algorithm by its registry name, ignoring case.
+pub fn find_selector(name: &str) -> Option<Box<dyn Selector>> {
+    find_selector_with(name, &SorterOptions::default())
+}
+
+/// Looks up a selection algorithm by its registry name, built with `options`.
+pub fn find_selector_with(name: &str, options: &SorterOptions) -> Option<Box<dyn Selector>> {
+    selectors_with(options)
+        .into_iter()
+        .find(|selector| selector.name().eq_ignore_ascii_case(name.trim()))
+}
+
+/// Result of checking a selection run against its input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SelectionCheck {
+    pub rank: usize,        // Index the value was selected for, counted from 0
+    pub found: Option<i32>, // Value at that index after the run
+    pub correct: bool,      // Value is the one sorted order has at `rank`
+    pub partitioned: bool,  // No larger value before it and no smaller one after it
+    pub permutation: bool,  // Output holds exactly the input values
+}
+
+impl SelectionCheck {
+    /// Checks that `trace` left the value of rank `k` at index `k`, split from the rest.
+    pub fn check(trace: &SortTrace, k: usize) -> Self {
+        let mut input = trace.initial.clone();
+        let mut output = trace.output.clone();
+        input.sort_unstable();
+        let found = output.get(k).copied();
+        let partitioned = found.is_some_and(|value| {
+            output[..k].iter().all(|&other| other <= value)
+                && output[k + 1..].iter().all(|&other| other >= value)
+        });
+        let correct = found.is_some() && found == input.get(k).copied();
+        output.sort_unstable();
+        Self {
+            rank: k,
+            found,
+            correct,
+            partitioned,
+            permutation: input == output,
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.correct && self.partitioned && self.permutation
+    }
+}
+
+impl fmt::Display for SelectionCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut problems = Vec::new();
+        if !self.correct {
+            problems.push("wrong value selected");
+        }
+        if !self.partitioned {
+            problems.push("not partitioned around it");
+        }
+        if !self.permutation {
+            problems.push("not a permutation of the input");
+        }
+        if !problems.is_empty() {
+            return write!(f, "FAILED, {}", problems.join(", "));
+        }
+        write!(
+            f,
+            "verified rank {} is {}",
+            self.rank + 1,
+            self.found.unwrap_or_default()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sorting_algorithms::{Distribution, PivotRule, SortEvent};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn selectors_find_every_rank() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let mut every = selectors();
+        for pivot in PivotRule::ALL {
+            every.push(Box::new(QuickSelect::new(pivot)));
+            every.push(Box::new(IntroSelect::new(pivot)));
+        }
+        for distribution in Distribution::ALL {
+            let len = rng.gen_range(1..=40);
+            let values = distribution.generate(&mut rng, len, -20, 20);
+            for selector in every.iter_mut() {
+                for k in 0..len {
+                    let trace = selector.record(&values, k);
+                    let check = SelectionCheck::check(&trace, k);
+                    assert!(
+                        check.passed(),
+                        "{} rank {} of {:?}: {}",
+                        selector.name(),
+                        k,
+                        values,
+                        check
+                    );
+                    assert!(
+                        trace.events.contains(&SortEvent::Selected(k)),
+                        "{} did not mark rank {}",
+                        selector.name(),
+                        k
+                    );
+                }
+                // Ranks past the end find nothing, but must not panic or lose values
+                for k in [len, len + 3] {
+                    let trace = selector.record(&values, k);
+                    let check = SelectionCheck::check(&trace, k);
+                    assert_eq!(check.found, None, "{} rank {}", selector.name(), k);
+                    assert!(check.permutation, "{} rank {}", selector.name(), k);
+                }
+            }
+        }
+    }
+}
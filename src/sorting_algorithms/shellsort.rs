@@ -0,0 +1,146 @@
+use crate::sorting_algorithms::{Complexity, SortInfo, SortRecorder, Sorter};
+use std::cmp::Ordering;
+
+/// Sequence of gaps a `ShellSort` works through, largest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GapSequence {
+    Shell,     // n/2, n/4, ..., 1
+    Knuth,     // 1, 4, 13, 40, ... (3^k - 1) / 2
+    Sedgewick, // 1, 8, 23, 77, ... 4^k + 3 * 2^(k-1) + 1
+    Ciura,     // 1, 4, 10, 23, 57, 132, 301, 701, then about 2.25 times the last
+    Tokuda,    // 1, 4, 9, 20, 46, ... ceil((9^k - 4^k) / (5 * 4^(k-1)))
+}
+
+impl GapSequence {
+    pub const ALL: [GapSequence; 5] = [
+        Self::Shell,
+        Self::Knuth,
+        Self::Sedgewick,
+        Self::Ciura,
+        Self::Tokuda,
+    ];
+
+    /// Registry key used to pick the sequence at runtime.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Shell => "shell",
+            Self::Knuth => "knuth",
+            Self::Sedgewick => "sedgewick",
+            Self::Ciura => "ciura",
+            Self::Tokuda => "tokuda",
+        }
+    }
+
+    /// Looks up a sequence by its registry name, ignoring case.
+    pub fn find(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|gaps| gaps.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Returns the gaps below `len`, largest first and always ending in 1.
+    pub fn gaps(&self, len: usize) -> Vec<usize> {
+        let mut gaps = match self {
+            Self::Shell => {
+                let mut gaps: Vec<usize> = std::iter::successors(Some(len / 2), |gap| {
+                    Some(gap / 2).filter(|&gap| gap > 0)
+                })
+                .collect();
+                gaps.reverse();
+                gaps
+            }
+            Self::Knuth => Self::ascending(|k| (3usize.pow(k) - 1) / 2, len),
+            Self::Sedgewick => {
+                let mut gaps = vec![1];
+                gaps.extend(Self::ascending(
+                    |k| 4usize.pow(k) + 3 * 2usize.pow(k - 1) + 1,
+                    len,
+                ));
+                gaps
+            }
+            Self::Ciura => {
+                let mut gaps = vec![1, 4, 10, 23, 57, 132, 301, 701];
+                while gaps[gaps.len() - 1] < len {
+                    gaps.push(gaps[gaps.len() - 1] * 9 / 4);
+                }
+                gaps
+            }
+            Self::Tokuda => Self::ascending(
+                |k| {
+                    let k = k as i32;
+                    ((9f64.powi(k) - 4f64.powi(k)) / (5.0 * 4f64.powi(k - 1))).ceil() as usize
+                },
+                len,
+            ),
+        };
+        gaps.retain(|&gap| gap > 0 && (gap < len || gap == 1));
+        gaps.dedup();
+        gaps.reverse();
+        gaps
+    }
+
+    // Collects `gap(1), gap(2), ...` while the gaps stay below `len`
+    fn ascending(gap: impl Fn(u32) -> usize, len: usize) -> Vec<usize> {
+        (1..).map(gap).take_while(|&gap| gap < len.max(2)).collect()
+    }
+}
+
+pub struct ShellSort {
+    pub gaps: GapSequence,
+}
+
+impl Default for ShellSort {
+    fn default() -> Self {
+        Self::new(GapSequence::Ciura)
+    }
+}
+
+impl Sorter for ShellSort {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "shellsort",
+            title: match self.gaps {
+                GapSequence::Shell => "Shell Sort Algorithm (Shell gaps)",
+                GapSequence::Knuth => "Shell Sort Algorithm (Knuth gaps)",
+                GapSequence::Sedgewick => "Shell Sort Algorithm (Sedgewick gaps)",
+                GapSequence::Ciura => "Shell Sort Algorithm (Ciura gaps)",
+                GapSequence::Tokuda => "Shell Sort Algorithm (Tokuda gaps)",
+            },
+            stable: false,
+            in_place: true,
+            complexity: Complexity {
+                best: "O(n log n)",
+                average: "depends on gaps",
+                worst: "O(n^2)",
+                space: "O(1)",
+            },
+        }
+    }
+
+    fn sort(&mut self, array: &mut SortRecorder) {
+        for gap in self.gaps.gaps(array.len()) {
+            array.gap(gap);
+            // Insertion sort every subsequence of values `gap` apart
+            for i in gap..array.len() {
+                let key = array[i];
+                let mut hole = i;
+                array.current(hole);
+                while hole >= gap
+                    && array.compare_values(hole - gap, array[hole - gap], hole, key)
+                        == Ordering::Greater
+                {
+                    array.write(hole, array[hole - gap]);
+                    hole -= gap;
+                    array.current(hole);
+                }
+                array.write(hole, key);
+            }
+        }
+    }
+}
+
+impl ShellSort {
+    pub fn new(gaps: GapSequence) -> Self {
+        ShellSort { gaps }
+    }
+}
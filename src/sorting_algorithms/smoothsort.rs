@@ -0,0 +1,113 @@
+use crate::sorting_algorithms::{
+    heap_tree::leonardo, Complexity, HeapShape, SortInfo, SortRecorder, Sorter,
+};
+
+/// Dijkstra's smoothsort. The array is kept as a row of max-heaps shaped as Leonardo trees,
+/// with the roots in ascending order, so the largest value is always the last one. Sorted input
+/// leaves every tree in order and takes linear time.
+#[derive(Default)]
+pub struct SmoothSort;
+
+impl Sorter for SmoothSort {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "smoothsort",
+            title: "Smoothsort Algorithm",
+            stable: false,
+            in_place: true,
+            complexity: Complexity {
+                best: "O(n)",
+                average: "O(n log n)",
+                worst: "O(n log n)",
+                space: "O(1)",
+            },
+        }
+    }
+
+    fn sort(&mut self, array: &mut SortRecorder) {
+        let len = array.len();
+        // Orders of the trees from left to right, each root at the right end of its tree
+        let mut orders: Vec<usize> = Vec::new();
+        array.phase("building");
+        for end in 1..=len {
+            match orders[..] {
+                [.., left, right] if left == right + 1 => {
+                    orders.truncate(orders.len() - 2);
+                    orders.push(left + 1);
+                }
+                [.., 1] => orders.push(0),
+                _ => orders.push(1),
+            }
+            array.shaped_heap(HeapShape::Leonardo, 0, end, end);
+            Self::rectify(array, &orders, orders.len() - 1);
+        }
+
+        array.phase("dequeuing");
+        for end in (1..len).rev() {
+            // The root of the last tree is the largest value and already in place
+            let order = orders.pop().unwrap_or(0);
+            array.shaped_heap(HeapShape::Leonardo, 0, end, len);
+            if order >= 2 {
+                // The two subtrees become trees of their own, and their roots find their places
+                orders.push(order - 1);
+                orders.push(order - 2);
+                Self::rectify(array, &orders, orders.len() - 2);
+                Self::rectify(array, &orders, orders.len() - 1);
+            }
+        }
+        array.shaped_heap(HeapShape::Leonardo, 0, 0, 0);
+    }
+}
+
+impl SmoothSort {
+    pub fn new() -> Self {
+        SmoothSort
+    }
+    // Moves the root of tree `tree` left past larger roots, then sifts down the value it
+    // leaves in the tree where it stops
+    fn rectify(array: &mut SortRecorder, orders: &[usize], tree: usize) {
+        let mut roots = Vec::with_capacity(orders.len());
+        let mut end = 0;
+        for &order in orders {
+            end += leonardo(order);
+            roots.push(end - 1);
+        }
+        let mut tree = tree;
+        while tree > 0 {
+            let (root, previous) = (roots[tree], roots[tree - 1]);
+            if !array.less(root, previous) {
+                break;
+            }
+            // The previous root can only come down if it is also larger than both children
+            if let Some((left, right)) = Self::children(root, orders[tree]) {
+                if !array.less(left, previous) || !array.less(right, previous) {
+                    break;
+                }
+            }
+            array.swap(root, previous);
+            tree -= 1;
+        }
+        Self::sift_down(array, roots[tree], orders[tree]);
+    }
+    // Sifts the root of a Leonardo tree of `order` down to its place within the tree
+    fn sift_down(array: &mut SortRecorder, mut root: usize, mut order: usize) {
+        while let Some((left, right)) = Self::children(root, order) {
+            let (child, child_order) = if array.less(right, left) {
+                (left, order - 1)
+            } else {
+                (right, order - 2)
+            };
+            if !array.less(root, child) {
+                break;
+            }
+            array.swap(root, child);
+            root = child;
+            order = child_order;
+        }
+    }
+    // Roots of the two subtrees of the tree of `order` rooted at `root`, which are trees of
+    // orders one and two lower
+    fn children(root: usize, order: usize) -> Option<(usize, usize)> {
+        (order >= 2).then(|| (root - 1 - leonardo(order - 2), root - 1))
+    }
+}
@@ -0,0 +1,253 @@
+use crate::sorting_algorithms::{
+    BinaryInsertionSort, BitonicSort, BogoSort, BottomUpMergeSort, BubbleSort, BucketSort,
+    CocktailShakerSort, CombSort, CountingSort, CycleSort, DaryHeapSort, GapSequence, GnomeSort,
+    Heap, InPlaceMergeSort, InsertionSort, IntroSort, LsdRadixSort, MergeSort, MsdRadixSort,
+    NaturalMergeSort, OddEvenMergeSort, OddEvenSort, PancakeSort, PartitionScheme, PdqSort,
+    PivotRule, QuickSort, SelectionSort, ShellSort, SmoothSort, SortRecorder, SortTrace,
+    StoogeSort, TimSort, WeakHeapSort,
+};
+use std::time::Instant;
+
+/// Asymptotic costs of a sorting algorithm, written in big-O notation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Complexity {
+    pub best: &'static str,
+    pub average: &'static str,
+    pub worst: &'static str,
+    pub space: &'static str,
+}
+
+/// Static description of a sorting algorithm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortInfo {
+    pub name: &'static str,  // Registry key used to pick the algorithm at runtime
+    pub title: &'static str, // Title shown above the graph
+    pub stable: bool,
+    pub in_place: bool,
+    pub complexity: Complexity,
+}
+
+/// Common interface implemented by every sorting algorithm.
+pub trait Sorter {
+    fn info(&self) -> SortInfo;
+
+    fn name(&self) -> &'static str {
+        self.info().name
+    }
+
+    /// Title shown above the graph, naming the chosen variant for sorters that have options.
+    fn title(&self) -> String {
+        self.info().title.to_string()
+    }
+
+    /// Reseeds any randomness the algorithm uses, so seeded runs replay exactly.
+    fn reseed(&mut self, _seed: u64) {}
+
+    /// Sorts `array` in ascending order, recording every step on it.
+    fn sort(&mut self, array: &mut SortRecorder);
+
+    /// Sorts a copy of `values` and returns the recorded run, timed without any playback.
+    fn record(&mut self, values: &[i32]) -> SortTrace {
+        let mut array = SortRecorder::new(values.to_vec());
+        let start = Instant::now();
+        self.sort(&mut array);
+        let elapsed = start.elapsed();
+        let mut trace = array.into_trace();
+        trace.elapsed = elapsed;
+        trace
+    }
+}
+
+/// Choices for the sorting algorithms that come in more than one variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SorterOptions {
+    pub gaps: GapSequence,       // Gap sequence used by `ShellSort`
+    pub base: u32,               // Digit base of the radix sorts and the counting sort fallback
+    pub pivot: PivotRule,        // Pivot rule used by `QuickSort`
+    pub scheme: PartitionScheme, // Partition scheme used by `QuickSort`
+    pub arity: usize,            // Children per node of `DaryHeapSort`
+    pub shuffles: Option<usize>, // Shuffles `BogoSort` tries before giving up
+}
+
+impl Default for SorterOptions {
+    fn default() -> Self {
+        Self {
+            gaps: GapSequence::Ciura,
+            base: 10,
+            pivot: PivotRule::Last,
+            scheme: PartitionScheme::Lomuto,
+            arity: 3,
+            shuffles: None,
+        }
+    }
+}
+
+/// Returns one instance of every available sorting algorithm, with the default variants.
+pub fn sorters() -> Vec<Box<dyn Sorter>> {
+    sorters_with(&SorterOptions::default())
+}
+
+/// Returns one instance of every available sorting algorithm, set up with `options`.
+pub fn sorters_with(options: &SorterOptions) -> Vec<Box<dyn Sorter>> {
+    vec![
+        Box::new(QuickSort::with_options(options.pivot, options.scheme)),
+        Box::new(MergeSort::new()),
+        Box::new(Heap::new()),
+        Box::new(InsertionSort::new()),
+        Box::new(BinaryInsertionSort::new()),
+        Box::new(SelectionSort::new()),
+        Box::new(BubbleSort::new()),
+        Box::new(CocktailShakerSort::new()),
+        Box::new(ShellSort::new(options.gaps)),
+        Box::new(CountingSort::new(options.base)),
+        Box::new(LsdRadixSort::new(options.base)),
+        Box::new(MsdRadixSort::new(options.base)),
+        Box::new(BucketSort::new()),
+        Box::new(IntroSort::new()),
+        Box::new(PdqSort::new()),
+        Box::new(TimSort::new()),
+        Box::new(BottomUpMergeSort::new()),
+        Box::new(NaturalMergeSort::new()),
+        Box::new(InPlaceMergeSort::new()),
+        Box::new(CycleSort::new()),
+        Box::new(PancakeSort::new()),
+        Box::new(GnomeSort::new()),
+        Box::new(CombSort::new()),
+        Box::new(OddEvenSort::new()),
+        Box::new(StoogeSort::new()),
+        Box::new(BogoSort::new(options.shuffles)),
+        Box::new(BitonicSort::new()),
+        Box::new(OddEvenMergeSort::new()),
+        Box::new(SmoothSort::new()),
+        Box::new(WeakHeapSort::new()),
+        Box::new(DaryHeapSort::new(options.arity)),
+    ]
+}
+
+/// Looks up a sorting algorithm by its registry name, ignoring case.
+pub fn find_sorter(name: &str) -> Option<Box<dyn Sorter>> {
+    find_sorter_with(name, &SorterOptions::default())
+}
+
+/// Looks up a sorting algorithm by its registry name, ignoring case, set up with `options`.
+pub fn find_sorter_with(name: &str, options: &SorterOptions) -> Option<Box<dyn Sorter>> {
+    sorters_with(options)
+        .into_iter()
+        .find(|sorter| sorter.name().eq_ignore_ascii_case(name.trim()))
+}
+
+/// Returns the registry names of every available sorting algorithm.
+pub fn sorter_names() -> Vec<&'static str> {
+    sorters().iter().map(|sorter| sorter.name()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sorting_algorithms::{Distribution, GapSequence, SortStats, Verification};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    // Inputs of every distribution over a spread of lengths, including the empty and single value cases
+    fn random_inputs() -> Vec<Vec<i32>> {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut inputs = vec![vec![], vec![5]];
+        for distribution in Distribution::ALL {
+            for _ in 0..8 {
+                let len = rng.gen_range(2..=64);
+                let min = rng.gen_range(-50..=0);
+                let max = rng.gen_range(min..=50);
+                inputs.push(distribution.generate(&mut rng, len, min, max));
+            }
+        }
+        inputs
+    }
+
+    // Every sorter, with each variant of the sorters that have them
+    fn every_sorter() -> Vec<Box<dyn Sorter>> {
+        let mut every = sorters();
+        for gaps in GapSequence::ALL {
+            every.push(Box::new(ShellSort::new(gaps)));
+        }
+        for arity in [2, 4, 8] {
+            every.push(Box::new(DaryHeapSort::new(arity)));
+        }
+        for base in [2, 3, 16, 256] {
+            every.push(Box::new(LsdRadixSort::new(base)));
+            every.push(Box::new(MsdRadixSort::new(base)));
+            every.push(Box::new(CountingSort::new(base)));
+        }
+        for pivot in PivotRule::ALL {
+            for scheme in PartitionScheme::ALL {
+                every.push(Box::new(QuickSort::with_options(pivot, scheme)));
+            }
+        }
+        every
+    }
+
+    #[test]
+    fn sorters_output_sorted_permutations() {
+        for mut sorter in every_sorter() {
+            for values in random_inputs() {
+                let trace = sorter.record(&values);
+                let mut expected = values.clone();
+                expected.sort_unstable();
+                let verification = Verification::check(sorter.as_mut(), &trace);
+                // A sorter may only stop short by saying it gave up, keeping the input values
+                if verification.gave_up.is_some() {
+                    assert!(verification.passed(), "{} on {:?}", sorter.name(), values);
+                    assert_ne!(trace.output, expected, "{} on {:?}", sorter.name(), values);
+                    continue;
+                }
+                assert_eq!(trace.output, expected, "{} on {:?}", sorter.name(), values);
+
+                assert!(
+                    verification.passed(),
+                    "{} on {:?}: {}",
+                    sorter.name(),
+                    values,
+                    verification
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn sorters_not_in_place_report_their_aux_memory() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let values = Distribution::Random.generate(&mut rng, 64, -50, 50);
+        for mut sorter in every_sorter() {
+            let stats = SortStats::from_trace(&sorter.record(&values));
+            // In-place sorters may still hold a value or two, such as cycle sort's
+            assert!(
+                sorter.info().in_place || stats.aux_memory > 0,
+                "{} is not in place but used no aux memory",
+                sorter.name()
+            );
+        }
+    }
+
+    #[test]
+    fn sorters_handle_keys_across_the_full_i32_range() {
+        let values = vec![i32::MAX, 0, i32::MIN, i32::MAX];
+        let mut expected = values.clone();
+        expected.sort_unstable();
+        for mut sorter in every_sorter() {
+            let trace = sorter.record(&values);
+            assert_eq!(trace.output, expected, "{}", sorter.name());
+        }
+    }
+
+    #[test]
+    fn sorter_traces_replay_to_their_output() {
+        for mut sorter in every_sorter() {
+            for values in random_inputs() {
+                let trace = sorter.record(&values);
+                let mut replayed = trace.initial.clone();
+                trace.replay(|step, _| replayed = step.to_vec());
+                if !trace.events.is_empty() {
+                    assert_eq!(replayed, trace.output, "{} on {:?}", sorter.name(), values);
+                }
+            }
+        }
+    }
+}
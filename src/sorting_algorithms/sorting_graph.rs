@@ -1,21 +1,129 @@
-use crate::audio::AudioSignal;
+use crate::{
+    audio::AudioSignal,
+    sorting_algorithms::{
+        Distribution, HeapTree, NetworkDiagram, SearchCheck, Searcher, SelectionCheck, Selector,
+        SortEvent, SortStats, SortTrace, Sorter, Verification,
+    },
+};
 use colored::Colorize;
-use rand::Rng;
+use crossterm::terminal;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use std::{
     sync::mpsc::Sender,
     thread::{self, JoinHandle},
     time::{Duration, Instant},
 };
+/// Describes the values a `SortGraph` is filled with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortConfig {
+    pub len: usize,                 // Number of values to generate
+    pub min: i32,                   // Smallest value to generate
+    pub max: i32,                   // Largest value to generate
+    pub distribution: Distribution, // Shape of the generated values
+    pub values: Option<Vec<i32>>,   // Explicit input, used instead of generated values
+}
+
+impl Default for SortConfig {
+    fn default() -> Self {
+        Self {
+            len: 31,
+            min: 0,
+            max: 50,
+            distribution: Distribution::Random,
+            values: None,
+        }
+    }
+}
+
+impl SortConfig {
+    /// Uses `values` as the input, with the length and range taken from them.
+    pub fn from_values(values: Vec<i32>) -> Self {
+        Self {
+            len: values.len(),
+            min: values.iter().copied().min().unwrap_or(0),
+            max: values.iter().copied().max().unwrap_or(0),
+            distribution: Distribution::Random,
+            values: Some(values),
+        }
+    }
+}
+
+// How the bars are scaled to fit the terminal
+struct BarScale {
+    rows: i32,
+    low: i32,
+    high: i32,
+    bar: &'static str,
+    gap: &'static str,
+}
+
+impl BarScale {
+    // Scales `low..=high` to at most `rows` rows, one row per value when they fit. Spans are
+    // taken in i64, as the full i32 range does not fit in an i32.
+    fn new(low: i32, high: i32, rows: i32, bar: &'static str, gap: &'static str) -> Self {
+        let span = high as i64 - low as i64 + 1;
+        BarScale {
+            rows: span.min(rows as i64).max(2) as i32,
+            low,
+            high,
+            bar,
+            gap,
+        }
+    }
+    // Number of rows filled by the bar of `value`, at least one
+    fn height(&self, value: i32) -> i32 {
+        if self.high <= self.low {
+            return self.rows;
+        }
+        let span = self.high as i64 - self.low as i64;
+        let scaled = (value as i64 - self.low as i64) * (self.rows - 1) as i64 / span;
+        1 + scaled.clamp(0, self.rows as i64 - 1) as i32
+    }
+    // Whether the bar of `value` reaches row `y`, counted from the top
+    fn filled(&self, value: i32, y: i32) -> bool {
+        self.height(value) >= self.rows - y
+    }
+}
+
 pub struct SortGraph<'a, 'b> {
     pub title: String,
     pub values: Vec<i32>,
-    pub max_height: i32,
+    pub config: SortConfig,
     pub audio_sender: &'a mut Option<Sender<AudioSignal>>, // Audio sender for live updates
     pub audio_handle: &'b mut Option<JoinHandle<()>>,      // Audio thread handle
+    pub headless: bool,                                    // Skip all drawing, audio and delays
+    pub speed: f32,                                        // Animation speed multiplier
+    pub seed: u64,                                         // Seed of `rng`, so runs can be replayed
+    pub stats: SortStats, // Operations counted so far in the current run
+    pub verification: Option<Verification>, // Outcome of the last finished run
+    pub selection: Option<SelectionCheck>, // Outcome of the last finished selection run
+    pub search: Option<SearchCheck>, // Outcome of the last finished search run
+    bucket_rows: i32,     // Lines under the bars kept for the bucket view of the current run
+    aux_view: bool,       // Whether the current run is drawn with its auxiliary buffer
+    search_view: bool,    // Whether the current run is a search, drawn with its target
+    heap_tree: Option<HeapTree>, // Tree drawn right of the bars when the current run builds a heap
+    network: Option<NetworkDiagram>, // Comparators drawn under the bars for a sorting network
+    rng: StdRng,
 }
 
-const WIDTH: i32 = 30;
-const HEIGHT: i32 = 50;
+// Colors cycled through for the subsequences of a gapped pass
+const SUBSEQUENCE_COLORS: [(u8, u8, u8); 8] = [
+    (230, 120, 120),
+    (120, 200, 120),
+    (120, 150, 240),
+    (230, 200, 100),
+    (200, 120, 220),
+    (100, 210, 210),
+    (240, 160, 80),
+    (170, 170, 250),
+];
+
+// Lines kept free around the bars for the title, the stats panel and the cursor
+const RESERVED_ROWS: i32 = 4;
+// Space between the bars and the heap tree
+const TREE_MARGIN: &str = "   ";
+// Pause on each probe of a search, which takes few enough steps to follow one at a time
+const PROBE_MILLIS: u64 = 300;
 
 impl<'a, 'b> SortGraph<'a, 'b> {
     /// Creates a new `SortGraph` with randomly generated values.
@@ -24,24 +132,130 @@ impl<'a, 'b> SortGraph<'a, 'b> {
         audio_sender: &'a mut Option<Sender<AudioSignal>>,
         audio_handle: &'b mut Option<JoinHandle<()>>,
     ) -> Self {
-        let mut rng = rand::thread_rng();
-        let values = (0..=WIDTH).map(|_| rng.gen_range(0..=HEIGHT)).collect();
-        SortGraph {
+        Self::with_config(title, SortConfig::default(), audio_sender, audio_handle)
+    }
+    /// Creates a new `SortGraph` filled according to `config`.
+    pub fn with_config(
+        title: &str,
+        config: SortConfig,
+        audio_sender: &'a mut Option<Sender<AudioSignal>>,
+        audio_handle: &'b mut Option<JoinHandle<()>>,
+    ) -> Self {
+        let seed = rand::thread_rng().gen();
+        let mut graph = SortGraph {
             title: title.to_string(),
-            values,
-            max_height: HEIGHT,
+            values: Vec::new(),
+            config,
             audio_sender,
             audio_handle,
-        }
+            headless: false,
+            speed: 1.0,
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+            stats: SortStats::default(),
+            bucket_rows: 0,
+            aux_view: false,
+            search_view: false,
+            heap_tree: None,
+            network: None,
+            verification: None,
+            selection: None,
+            search: None,
+        };
+        graph.reset();
+        graph
     }
+    /// Refills the values from the config, drawing new random ones unless the input is explicit.
     pub fn reset(&mut self) {
-        let mut rng = rand::thread_rng();
-        let values = (0..=WIDTH).map(|_| rng.gen_range(0..=HEIGHT)).collect();
-        self.values = values;
+        if let Some(values) = &self.config.values {
+            self.values = values.clone();
+            return;
+        }
+        let config = &self.config;
+        self.values =
+            config
+                .distribution
+                .generate(&mut self.rng, config.len, config.min, config.max);
+    }
+    pub fn set_config(&mut self, config: SortConfig) {
+        self.config = config;
+        self.reset();
+    }
+    /// Reseeds the random number generator and regenerates the values from it.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = StdRng::seed_from_u64(seed);
+        self.reset();
     }
     pub fn set_title(&mut self, title: &str) {
         self.title = title.to_string();
     }
+    /// Turns off drawing, audio and delays. Runs still return their full trace.
+    pub fn set_headless(&mut self, headless: bool) {
+        self.headless = headless;
+    }
+    /// Sets the animation speed multiplier, 2.0 plays twice as fast.
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed;
+    }
+    /// Returns the lowest and highest value the bars have to cover.
+    pub fn value_range(&self) -> (i32, i32) {
+        let low = self.values.iter().copied().min().unwrap_or(0);
+        let high = self.values.iter().copied().max().unwrap_or(0);
+        (low.min(self.config.min).min(0), high.max(self.config.max))
+    }
+    // Maps a value onto the audible range, up to 880 Hz for the highest value
+    fn frequency(&self, value: i32) -> f32 {
+        let max_frequency = 880.0;
+        let (low, high) = self.value_range();
+        if high <= low {
+            return max_frequency;
+        }
+        max_frequency * (value as f32 - low as f32) / (high as f32 - low as f32)
+    }
+    // Columns and rows of the terminal left for the bars and the views drawn with them
+    fn free_space(&self) -> (usize, i32) {
+        match terminal::size() {
+            Ok((columns, rows)) => (
+                columns as usize,
+                rows as i32 - RESERVED_ROWS - self.bucket_rows,
+            ),
+            Err(_) => (usize::MAX, i32::MAX),
+        }
+    }
+    // Lines given to the comparator network, which takes up to half the free rows under a
+    // line naming the active layer
+    fn network_rows(&self) -> i32 {
+        let (_, rows) = self.free_space();
+        self.network.as_ref().map_or(0, |network| {
+            (network.line_count() as i32).min((rows - 1) / 2)
+        })
+    }
+    // Fits the bars to the terminal, one row per value when there is room for it
+    fn bar_scale(&self) -> BarScale {
+        let (low, high) = self.value_range();
+        let (mut columns, mut rows) = self.free_space();
+        if self.network.is_some() {
+            rows -= self.network_rows() + 1;
+        }
+        if let Some(tree) = &self.heap_tree {
+            columns = columns.saturating_sub(tree.columns + TREE_MARGIN.len());
+        }
+        if self.aux_view {
+            // The auxiliary buffer gets as many rows as the array, under a line naming it
+            rows = (rows - 1) / 2;
+        }
+        let (bar, gap) = if self.values.len() * 3 <= columns {
+            ("[x]", "   ")
+        } else {
+            ("█", " ")
+        };
+        BarScale::new(low, high, rows, bar, gap)
+    }
+    // Scales a delay by the animation speed
+    fn delay(&self, millis: u64) -> Duration {
+        Duration::from_secs_f32(millis as f32 / 1000.0 / self.speed)
+    }
     pub fn update_audio(&self, frequency: f32) {
         if let Some(ref sender) = self.audio_sender {
             sender
@@ -62,17 +276,17 @@ impl<'a, 'b> SortGraph<'a, 'b> {
         }
     }
     fn send_swap_values(&self, from: i32, to: i32, duration: u64) {
-        let max_frequency = 880.0; // Maximum frequency to represent max_height
+        if self.headless {
+            return;
+        }
+        let duration = self.delay(duration).as_millis() as u64;
         let half = duration / 2;
-        let max_height = self.max_height as f32;
-
-        let scale_frequency = |value: i32| -> f32 { max_frequency * (value as f32 / max_height) };
         let start_time = Instant::now();
         // Send frequencies to the audio handler one at a time
         if let Some(ref sender) = self.audio_sender {
-            // Scale 'from' and 'to' values based on max_height
-            let from_frequency = scale_frequency(from);
-            let to_frequency = scale_frequency(to);
+            // Scale 'from' and 'to' values to the value range
+            let from_frequency = self.frequency(from);
+            let to_frequency = self.frequency(to);
             sender
                 .send(AudioSignal::Single(from_frequency))
                 .unwrap_or_default();
@@ -93,17 +307,17 @@ impl<'a, 'b> SortGraph<'a, 'b> {
     }
 
     pub fn play_graph(&self, duration: u64) {
-        let duration_per_value = duration / self.values.len() as u64;
-        let max_frequency = 880.0; // Maximum frequency to represent max_height
-        let max_height = self.max_height as f32;
-
-        let scale_frequency = |value: i32| -> f32 { max_frequency * (value as f32 / max_height) };
+        if self.headless {
+            return;
+        }
+        let duration = self.delay(duration).as_millis() as u64;
+        let duration_per_value = duration / self.values.len().max(1) as u64;
 
         let start_time = Instant::now();
         // Send frequencies to the audio handler one at a time
         if let Some(ref sender) = self.audio_sender {
             for val in self.values.iter() {
-                let frequency = scale_frequency(*val);
+                let frequency = self.frequency(*val);
                 sender
                     .send(AudioSignal::Single(frequency))
                     .unwrap_or_default();
@@ -120,6 +334,9 @@ impl<'a, 'b> SortGraph<'a, 'b> {
     }
     // Prints the map for visualization
     pub fn display_graph(&mut self) {
+        if self.headless {
+            return;
+        }
         let mut buffer = String::new();
         // Hide the cursor to avoid flickering
         buffer.push_str("\x1B[?25l");
@@ -132,17 +349,17 @@ impl<'a, 'b> SortGraph<'a, 'b> {
 
         // Print the title and move to the next line
         buffer.push_str(&format!("{}\n", self.title));
-        let height = self.max_height;
-        for y in 0..=height {
+        let scale = self.bar_scale();
+        for y in 0..scale.rows {
             for val in self.values.iter() {
-                let y_pos = height - y;
-                buffer += match y_pos {
-                    pos if *val >= pos => "[x]",
-                    _ => "   ",
+                buffer += match scale.filled(*val, y) {
+                    true => scale.bar,
+                    false => scale.gap,
                 };
             }
             buffer.push('\n'); // Add a new line at the end of each row
         }
+        buffer += &self.stats_panel();
         // Show the cursor again
         buffer.push_str("\x1B[?25h");
 
@@ -151,157 +368,617 @@ impl<'a, 'b> SortGraph<'a, 'b> {
         println!("{}", buffer);
         self.play_graph(2000);
     }
-    pub fn display_graph_with_highlights(
-        &self,
-        pivot_index: usize,
-        low: i32,
-        high: i32,
-        swap: (usize, usize),
-    ) {
-        let height = self.max_height;
-        let mut buffer = String::new();
-        let swap_frequencies_from: i32 = self.values[swap.0];
-        let swap_frequencies_to: i32 = self.values[swap.1];
-
-        // Hide the cursor to avoid flickering
-        // swap = (a, b), columns is swapping from index a to index b
-        buffer.push_str("\x1B[?25l");
-
-        // Move cursor to the top-left
-        buffer.push_str("\x1B[H");
-
-        // Print the title and move to the next line
-        buffer.push_str(&format!("{}\n", self.title));
-        for y in 0..=height {
-            for (x, val) in self.values.iter().enumerate() {
-                let symbol = if *val >= height - y { "[x]" } else { "   " };
-                let styled_symbol = if x == pivot_index {
-                    symbol.red().on_truecolor(128, 128, 128)
-                } else if x as i32 >= low && x as i32 <= high {
-                    // is in the partition
-                    if swap.0 == x || swap.1 == x {
-                        // column is in partition
-                        symbol.bright_yellow().on_truecolor(128, 128, 128) // this column is a swap value
-                    } else {
-                        symbol.on_truecolor(128, 128, 128) // this column is not a swap value
-                    }
-                } else {
-                    // column is not in the partition
-                    symbol.white()
-                };
-
-                if self.max_height - y == self.values[pivot_index] {
-                    // blue bar across graph equalling the pivot column's height
-                    buffer += &format!("{}", styled_symbol.on_blue());
-                } else {
+    /// Records a run of `sorter` over the current values, replays it on the graph and
+    /// finishes with a sweep showing whether the output was verified.
+    pub fn sort_with(&mut self, sorter: &mut dyn Sorter) -> SortTrace {
+        sorter.reseed(self.rng.gen());
+        let trace = sorter.record(&self.values);
+        self.verification = None;
+        self.selection = None;
+        self.search = None;
+        self.play(&trace);
+        let verification = Verification::check(sorter, &trace);
+        self.verification = Some(verification);
+        self.display_sweep(&verification);
+        trace
+    }
+    /// Records a run of `selector` looking for the value of rank `k` over the current values,
+    /// and replays it on the graph, ending on a frame with the value found marked.
+    pub fn select_with(&mut self, selector: &mut dyn Selector, k: usize) -> SortTrace {
+        selector.reseed(self.rng.gen());
+        let trace = selector.record(&self.values, k);
+        let selection = SelectionCheck::check(&trace, k);
+        self.verification = None;
+        self.selection = None;
+        self.search = None;
+        if self.headless {
+            self.play(&trace);
+            self.selection = Some(selection);
+            return trace;
+        }
+        self.prepare(&trace);
+        self.display_graph();
+        let highlights = self.animate(&trace);
+        // The last frame shows the check beside the counts
+        self.selection = Some(selection);
+        if let Some(event) = trace.events.last() {
+            self.display_step(&highlights, event);
+        }
+        println!();
+        trace
+    }
+    /// Sorts the current values if they are not already, records a run of `searcher` looking
+    /// for `target` in them, and replays it on the graph, ending on a frame with the value
+    /// found marked.
+    pub fn search_with(&mut self, searcher: &mut dyn Searcher, target: i32) -> SortTrace {
+        if !self.values.is_sorted() {
+            self.values.sort_unstable();
+        }
+        let trace = searcher.record(&self.values, target);
+        let search = SearchCheck::check(&trace, target);
+        self.verification = None;
+        self.selection = None;
+        self.search = None;
+        if self.headless {
+            self.play(&trace);
+            self.search = Some(search);
+            return trace;
+        }
+        self.prepare(&trace);
+        self.display_graph();
+        let highlights = self.animate(&trace);
+        // The last frame shows where the target was found, or that it is not there
+        self.search = Some(search);
+        if let Some(event) = trace.events.last() {
+            self.display_step(&highlights, event);
+        }
+        println!();
+        trace
+    }
+    /// Picks one of the current values to search for, or 0 when there are none.
+    pub fn random_target(&mut self) -> i32 {
+        match self.values.is_empty() {
+            true => 0,
+            false => self.values[self.rng.gen_range(0..self.values.len())],
+        }
+    }
+    /// Replays a recorded run, drawing one frame for every step that touches the array.
+    pub fn play(&mut self, trace: &SortTrace) {
+        if self.headless {
+            self.values = trace.output.clone();
+            self.stats = SortStats::from_trace(trace);
+            return;
+        }
+        self.prepare(trace);
+        self.display_graph();
+        self.animate(trace);
+        self.display_graph();
+    }
+    /// Replays a single operation on values already on screen, such as one priority queue
+    /// operation, without the pauses that open and close a full run.
+    pub fn play_operation(&mut self, trace: &SortTrace) {
+        if self.headless {
+            self.values = trace.output.clone();
+            self.stats = SortStats::from_trace(trace);
+            return;
+        }
+        self.prepare(trace);
+        // Clear whatever the previous operation left below the bars
+        print!("\x1B[H\x1B[J");
+        let highlights = self.animate(trace);
+        // One more frame, so operations that only look at the values still show up
+        if let Some(event) = trace.events.last() {
+            self.display_step(&highlights, event);
+        }
+        println!();
+    }
+    // Resets the values and counts to the start of `trace`, and sets aside room for the views
+    // it uses
+    fn prepare(&mut self, trace: &SortTrace) {
+        self.values = trace.initial.clone();
+        self.stats = SortStats::new(trace.elapsed);
+        let uses_buckets = trace
+            .events
+            .iter()
+            .any(|event| matches!(event, SortEvent::Buckets(count) if *count > 0));
+        self.bucket_rows = if uses_buckets { 2 } else { 0 };
+        self.aux_view = trace
+            .events
+            .iter()
+            .any(|event| matches!(event, SortEvent::Stash { .. }));
+        let heap = trace
+            .events
+            .iter()
+            .filter_map(|event| match *event {
+                SortEvent::Heap {
+                    start, end, shape, ..
+                } => Some((end - start, shape)),
+                _ => None,
+            })
+            .max_by_key(|&(len, _)| len);
+        self.heap_tree = heap.filter(|&(len, _)| len > 0).map(|(len, shape)| {
+            let (low, high) = self.value_range();
+            let columns = terminal::size().map_or(80, |(columns, _)| columns as usize);
+            HeapTree::new(shape, len, low, high, columns / 2)
+        });
+        self.network = NetworkDiagram::from_trace(trace);
+        self.search_view = trace
+            .events
+            .iter()
+            .any(|event| matches!(event, SortEvent::Target(_)));
+    }
+    // Draws a frame for every event of `trace` that touches the array, returning the
+    // highlights in effect at the end
+    fn animate(&mut self, trace: &SortTrace) -> Highlights {
+        let mut highlights = Highlights::default();
+        for event in trace.events.iter() {
+            event.apply(&mut self.values);
+            self.stats.count(event);
+            highlights.update(event, &self.values);
+            match *event {
+                SortEvent::Compare(..)
+                | SortEvent::Swap(..)
+                | SortEvent::Write { .. }
+                | SortEvent::Push(_)
+                | SortEvent::Pop
+                | SortEvent::Deposit { .. }
+                | SortEvent::Stash { .. }
+                | SortEvent::Probe(_) => self.display_step(&highlights, event),
+                _ => {}
+            }
+        }
+        highlights
+    }
+    // Colors the bars green from left to right while they are in order, and red from where
+    // the output went wrong or when the run failed verification
+    fn display_sweep(&self, verification: &Verification) {
+        if self.headless {
+            return;
+        }
+        let scale = self.bar_scale();
+        let in_order = |x: usize| x == 0 || self.values[x - 1] <= self.values[x];
+        for swept in 0..self.values.len() {
+            let mut buffer = String::new();
+            // Hide the cursor and move it to the top-left
+            buffer.push_str("\x1B[?25l\x1B[H");
+            buffer.push_str(&format!("{}\n", self.title));
+            for y in 0..scale.rows {
+                for (x, val) in self.values.iter().enumerate() {
+                    let symbol = match scale.filled(*val, y) {
+                        true => scale.bar,
+                        false => scale.gap,
+                    };
+                    let styled_symbol = match x <= swept {
+                        false => symbol.clear(),
+                        true if verification.passed() && in_order(x) => symbol.green(),
+                        true => symbol.red(),
+                    };
                     buffer += &format!("{}", styled_symbol);
                 }
+                buffer.push('\n');
             }
-            buffer.push('\n');
+            buffer += &self.stats_panel();
+            buffer.push_str("\x1B[?25h");
+            print!("{}", buffer);
+            self.update_audio(self.frequency(self.values[swept]));
+            thread::sleep(self.delay(15));
         }
-        // Show the cursor again
-        buffer.push_str("\x1B[?25h");
-
-        // Print the entire buffer at once to the terminal
-        print!("{}", buffer);
-        self.send_swap_values(swap_frequencies_from, swap_frequencies_to, 25)
-    }
-    pub fn display_graph_move_highlights(
-        &self,
-        start: usize,
-        middle: usize,
-        end: usize,
-        swap: Option<(usize, usize)>,
-    ) {
-        let height = self.max_height;
-        let mut buffer = String::new();
-        let mut swap_frequencies_from: i32 = 0;
-        let mut swap_frequencies_to: i32 = 0;
-        if let Some((from, to)) = swap {
-            swap_frequencies_from = from as i32;
-            swap_frequencies_to = to as i32;
+        self.update_audio(0.0);
+        println!();
+    }
+    // One line of running operation counts shown under the bars, with the verification
+    // result once the run is over. Searches only count their probes.
+    fn stats_panel(&self) -> String {
+        let stats = &self.stats;
+        let outcome = self
+            .verification
+            .map(|verification| (verification.to_string(), verification.passed()))
+            .or_else(|| {
+                self.selection
+                    .map(|selection| (selection.to_string(), selection.passed()))
+            })
+            .or_else(|| {
+                self.search
+                    .map(|search| (search.to_string(), search.passed()))
+            });
+        // A sorter that gave up at its cap kept its values, but did not sort them either
+        let gave_up = self
+            .verification
+            .is_some_and(|verification| verification.gave_up.is_some() && !verification.sorted);
+        let verification = match outcome {
+            Some((outcome, true)) if gave_up => format!("  {}", outcome.yellow()),
+            Some((outcome, true)) => format!("  {}", outcome.green()),
+            Some((outcome, false)) => format!("  {}", outcome.red()),
+            None => String::new(),
+        };
+        if self.search_view {
+            return format!(
+                "Probes: {:<6} Search time: {:?}{}\x1B[K",
+                stats.probes, stats.elapsed, verification
+            );
+        }
+        format!(
+            "Comparisons: {:<6} Swaps: {:<6} Writes: {:<6} Aux memory: {:<4} Sort time: {:?}{}\x1B[K",
+            stats.comparisons, stats.swaps, stats.writes, stats.aux_memory, stats.elapsed, verification
+        )
+    }
+    // Two lines showing how full each bucket is, squeezing neighboring buckets into one column
+    // when there are more buckets than columns. The last bucket touched is green while it
+    // fills and red while it drains.
+    fn buckets_panel(highlights: &Highlights) -> String {
+        const LEVELS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+        let buckets = &highlights.buckets;
+        if buckets.is_empty() {
+            return "\x1B[K\n\x1B[K\n".to_string();
+        }
+        let columns = terminal::size().map_or(80, |(columns, _)| columns as usize);
+        let per_column = buckets.len().div_ceil(columns.max(1));
+        let peak = (highlights.bucket_peak * per_column).max(1);
+        let mut line = String::new();
+        for (column, group) in buckets.chunks(per_column).enumerate() {
+            let count: usize = group.iter().sum();
+            let level = LEVELS[(count * 8).div_ceil(peak).min(8)].to_string();
+            let touched = highlights
+                .bucket
+                .filter(|(bucket, _)| bucket / per_column == column);
+            line += &match touched {
+                Some((_, true)) => level.green().to_string(),
+                Some((_, false)) => level.red().to_string(),
+                None => level.cyan().to_string(),
+            };
+        }
+        let held: usize = buckets.iter().sum();
+        format!(
+            "{}\x1B[K\n{} buckets, values held: {}\x1B[K\n",
+            line,
+            buckets.len(),
+            held
+        )
+    }
+    // The auxiliary buffer drawn as a second row of bars at the same scale, one column per
+    // slot. The slot last filled is green.
+    fn aux_panel(highlights: &Highlights, scale: &BarScale, columns: usize) -> String {
+        let mut panel = format!("{}\x1B[K\n", "Auxiliary buffer".bright_black());
+        for y in 0..scale.rows {
+            for slot in 0..columns {
+                let held = highlights.aux.get(slot).copied().flatten();
+                let symbol = match held {
+                    Some(value) if scale.filled(value, y) => scale.bar,
+                    _ => scale.gap,
+                };
+                panel += &match highlights.aux_slot {
+                    Some((filled, true)) if filled == slot => symbol.green().to_string(),
+                    _ => symbol.cyan().to_string(),
+                };
+            }
+            panel += "\x1B[K\n";
         }
+        panel
+    }
+    fn display_step(&self, highlights: &Highlights, event: &SortEvent) {
+        let scale = self.bar_scale();
+        let mut buffer = String::new();
+        // (compared or source column, destination column)
+        let (from, to) = match *event {
+            SortEvent::Compare(i, j) | SortEvent::Swap(i, j) => (Some(i), Some(j)),
+            SortEvent::Write { index, .. } => (None, Some(index)),
+            SortEvent::Push(_) => (None, self.values.len().checked_sub(1)),
+            SortEvent::Deposit { index, .. }
+            | SortEvent::Stash { index, .. }
+            | SortEvent::Probe(index) => (Some(index), None),
+            _ => (None, None),
+        };
+        let is_compare = matches!(event, SortEvent::Compare(..) | SortEvent::Probe(_));
+        let value_at = |idx: Option<usize>| idx.and_then(|i| self.values.get(i).copied());
+        let swap_frequencies_from = value_at(from).unwrap_or(0);
+        // A probe sounds the probed value and then the target, closer together as it closes in
+        let (swap_frequencies_to, millis) = match *event {
+            SortEvent::Probe(_) => (highlights.target.unwrap_or(0), PROBE_MILLIS),
+            _ => (value_at(to).unwrap_or(0), 25),
+        };
+
         // Hide the cursor to avoid flickering
-        // swap = (a, b), columns is swapping from index a to index b
         buffer.push_str("\x1B[?25l");
 
         // Move cursor to the top-left
         buffer.push_str("\x1B[H");
 
         // Print the title and move to the next line
-        buffer.push_str(&format!("{}\n", self.title));
-        for y in 0..=height {
+        let mut title = self.title.clone();
+        if let Some(phase) = highlights.phase {
+            title += &format!(" - {}", phase.bright_cyan());
+        }
+        if let Some(gap) = highlights.gap {
+            title += &format!(" - gap {}", gap);
+        }
+        if let Some(value) = value_at(highlights.selected) {
+            let rank = highlights.selected.unwrap_or(0) + 1;
+            title += &format!(" - rank {}: {}", rank, value.to_string().bright_green());
+        }
+        if let Some(target) = highlights.target {
+            title += &match (highlights.found, self.search) {
+                (Some(index), _) => format!(
+                    " - found {} at index {}",
+                    target.to_string().bright_green(),
+                    index
+                ),
+                (None, Some(_)) => format!(" - {} is not there", target.to_string().red()),
+                (None, None) => {
+                    let (start, end) = highlights.focus.unwrap_or((0, self.values.len()));
+                    format!(" - looking for {} in {}..{}", target, start, end)
+                }
+            };
+            if let Some(probe) = highlights.probe {
+                title += &format!(" - probe {} at index {}", self.stats.probes, probe);
+            }
+        }
+        buffer.push_str(&format!("{}\x1B[K\n", title));
+        let tree_lines = self.heap_tree.zip(highlights.heap).map(|(tree, heap)| {
+            let rows = scale.rows as usize;
+            tree.lines(&self.values, heap, &highlights.flipped, event, rows)
+        });
+        let target = highlights
+            .target
+            .filter(|&target| target >= scale.low && target <= scale.high);
+        let pivot_heights: Vec<i32> = highlights
+            .pivots
+            .iter()
+            .filter_map(|&pivot| value_at(Some(pivot)))
+            .chain(target)
+            .map(|value| scale.height(value))
+            .collect();
+        for y in 0..scale.rows {
             for (x, val) in self.values.iter().enumerate() {
-                let symbol = if *val >= height - y { "[x]" } else { "   " };
-                let styled_symbol = match swap {
-                    Some((from, to)) if from == x && to != x => {
+                let symbol = if scale.filled(*val, y) {
+                    scale.bar
+                } else {
+                    scale.gap
+                };
+                let styled_symbol = match (from, to) {
+                    _ if highlights.selected == Some(x) || highlights.found == Some(x) => {
+                        symbol.black().on_bright_green()
+                    }
+                    _ if highlights.pivots.contains(&x) => symbol.red(),
+                    _ if highlights.current == Some(x) => symbol.bright_magenta(),
+                    _ if highlights.candidate == Some(x) => symbol.bright_cyan(),
+                    _ if is_compare && (from == Some(x) || to == Some(x)) => {
+                        symbol.bright_yellow() // this column is being compared
+                    }
+                    (Some(from), to) if from == x && to != Some(x) => {
                         symbol.bright_red() // this column is a swap value
                     }
-                    Some((_from, to)) if to == x => {
+                    (_, Some(to)) if to == x => {
                         symbol.green() // this column is a swap value
                     }
+                    // columns in the same gap-separated subsequence share a color
+                    _ if highlights.gap.is_some_and(|gap| gap > 1) => {
+                        let gap = highlights.gap.unwrap_or(1);
+                        let (r, g, b) = SUBSEQUENCE_COLORS[x % gap % SUBSEQUENCE_COLORS.len()];
+                        symbol.truecolor(r, g, b)
+                    }
                     _ => symbol.clear(),
                 };
-                if x >= start && x < middle {
-                    // highlight left side
-                    buffer += &format!("{}", styled_symbol.on_truecolor(140, 140, 140));
-                } else if x >= middle && x < end {
-                    // highlight right side
-                    buffer += &format!("{}", styled_symbol.on_truecolor(180, 180, 180));
+                let styled_symbol = match (highlights.focus, highlights.middle) {
+                    // highlight left side of a merge
+                    (Some((start, _)), Some(middle)) if x >= start && x < middle => {
+                        styled_symbol.on_truecolor(140, 140, 140)
+                    }
+                    // highlight right side of a merge
+                    (Some((_, end)), Some(middle)) if x >= middle && x < end => {
+                        styled_symbol.on_truecolor(180, 180, 180)
+                    }
+                    // column is in the partition
+                    (Some((start, end)), None) if x >= start && x < end => {
+                        styled_symbol.on_truecolor(128, 128, 128)
+                    }
+                    // column is in a range already in order
+                    _ if highlights.is_sorted(x) => styled_symbol.on_truecolor(30, 70, 30),
+                    // column is in a run, neighboring runs alternate shades
+                    _ => match highlights.run_at(x) {
+                        Some(run) if run % 2 == 0 => styled_symbol.on_truecolor(40, 50, 100),
+                        Some(_) => styled_symbol.on_truecolor(80, 50, 100),
+                        None => styled_symbol,
+                    },
+                };
+
+                if pivot_heights.contains(&(scale.rows - y)) {
+                    // blue bar across graph equalling each pivot column's height
+                    buffer += &format!("{}", styled_symbol.on_blue());
                 } else {
                     buffer += &format!("{}", styled_symbol);
                 }
             }
+            if let Some(lines) = &tree_lines {
+                buffer += TREE_MARGIN;
+                buffer += &lines[y as usize];
+            }
             buffer.push('\n');
         }
+        if let Some(network) = &self.network {
+            let layer = match highlights.layer {
+                Some(layer) => format!(" - layer {} of {}", layer + 1, network.layers.len()),
+                None => String::new(),
+            };
+            buffer += &format!("{}{}\x1B[K\n", "Comparator network".bright_black(), layer);
+            let cell = scale.bar.chars().count();
+            let rows = self.network_rows() as usize;
+            for line in network.lines(self.values.len(), highlights.layer, event, cell, rows) {
+                buffer += &format!("{}\x1B[K\n", line);
+            }
+        }
+        if self.aux_view {
+            buffer += &Self::aux_panel(highlights, &scale, self.values.len());
+        }
+        if self.bucket_rows > 0 {
+            buffer += &Self::buckets_panel(highlights);
+        }
+        buffer += &self.stats_panel();
         // Show the cursor again
         buffer.push_str("\x1B[?25h");
 
         // Print the entire buffer at once to the terminal
         print!("{}", buffer);
-        self.send_swap_values(swap_frequencies_from, swap_frequencies_to, 50)
+        self.send_swap_values(swap_frequencies_from, swap_frequencies_to, millis);
     }
-    pub fn display_simple_swap_graph(&self, swap: Option<(usize, usize)>) {
-        let height = self.max_height;
-        let mut buffer = String::new();
-
-        let mut swap_frequencies_from: i32 = 0;
-        let mut swap_frequencies_to: i32 = 0;
-        if let Some((from, to)) = swap {
-            swap_frequencies_from = from as i32;
-            swap_frequencies_to = to as i32;
-        }
-        // Hide the cursor to avoid flickering
-        // swap = (a, b), columns is swapping from index a to index b
-        buffer.push_str("\x1B[?25l");
+}
 
-        // Move cursor to the top-left
-        buffer.push_str("\x1B[H");
+/// Highlighting state carried between the frames of a replayed `SortTrace`.
+#[derive(Debug, Default)]
+struct Highlights {
+    pivots: Vec<usize>, // One pivot, or two for dual-pivot partitioning
+    focus: Option<(usize, usize)>,
+    middle: Option<usize>,
+    current: Option<usize>,
+    candidate: Option<usize>,
+    sorted: Vec<(usize, usize)>, // Disjoint ranges known to be in order
+    gap: Option<usize>,
+    buckets: Vec<usize>,           // Number of values in each bucket
+    bucket_peak: usize,            // Most values any bucket has held since they were set up
+    bucket: Option<(usize, bool)>, // Last bucket touched, and whether a value went in
+    phase: Option<&'static str>,
+    runs: Vec<(usize, usize)>, // Runs waiting to be merged, in array order
+    aux: Vec<Option<i32>>,     // Value held in each auxiliary slot
+    aux_slot: Option<(usize, bool)>, // Last auxiliary slot touched, and whether a value went in
+    heap: Option<(usize, usize, usize)>, // Heap range and the end of its sorted tail
+    flipped: Vec<bool>,        // Reverse bits of the weak heap nodes
+    layer: Option<usize>,      // Sorting network layer being run
+    selected: Option<usize>,   // Value a selection algorithm found, in its place
+    target: Option<i32>,       // Value a search is looking for
+    probe: Option<usize>,      // Last value a search compared with its target
+    found: Option<usize>,      // Index a search found its target at
+}
 
-        // Print the title and move to the next line
-        buffer.push_str(&format!("{}\n", self.title));
-        for y in 0..=height {
-            for (x, val) in self.values.iter().enumerate() {
-                let symbol = if *val >= height - y { "[x]" } else { "   " };
-                let styled_symbol = match swap {
-                    Some((from, to)) if from == x && to != x => {
-                        symbol.bright_red() // this column is a swap value
-                    }
-                    Some((_from, to)) if to == x => {
-                        symbol.green() // this column is a swap value
-                    }
-                    _ => symbol.clear(),
+impl Highlights {
+    fn update(&mut self, event: &SortEvent, values: &[i32]) {
+        match *event {
+            SortEvent::Pivot(idx) => self.pivots.push(idx),
+            SortEvent::Focus { start, end } => {
+                self.focus = Some((start, end));
+                self.middle = None;
+                self.pivots.clear();
+            }
+            SortEvent::Split { start, middle, end } => {
+                self.focus = Some((start, end));
+                self.middle = Some(middle);
+                self.pivots.clear();
+                // Two stacked runs being merged become one run
+                let halves = [(start, middle), (middle, end)];
+                if halves.iter().all(|half| self.runs.contains(half)) {
+                    self.runs.retain(|run| !halves.contains(run));
+                    self.runs.push((start, end));
+                    self.runs.sort_unstable();
+                }
+            }
+            SortEvent::Run { start, end } => {
+                self.runs.push((start, end));
+                self.runs.sort_unstable();
+            }
+            SortEvent::Phase(phase) => self.phase = Some(phase),
+            SortEvent::Current(idx) => self.current = Some(idx),
+            SortEvent::Candidate(idx) => self.candidate = Some(idx),
+            SortEvent::Sorted { start, end } => self.add_sorted(start, end),
+            SortEvent::Gap(gap) => self.gap = Some(gap),
+            SortEvent::Buckets(count) => {
+                self.buckets = vec![0; count];
+                self.bucket_peak = 0;
+                self.bucket = None;
+            }
+            SortEvent::Deposit { bucket, .. } => {
+                self.buckets[bucket] += 1;
+                self.bucket_peak = self.bucket_peak.max(self.buckets[bucket]);
+                self.bucket = Some((bucket, true));
+            }
+            SortEvent::Stash { index, slot } => {
+                if self.aux.len() <= slot {
+                    self.aux.resize(slot + 1, None);
+                }
+                self.aux[slot] = Some(values[index]);
+                self.aux_slot = Some((slot, true));
+            }
+            SortEvent::Heap {
+                start,
+                end,
+                sorted_end,
+                ..
+            } => self.heap = Some((start, end, sorted_end)).filter(|_| start < sorted_end),
+            SortEvent::Flip(index) => {
+                if self.flipped.len() <= index {
+                    self.flipped.resize(index + 1, false);
+                }
+                self.flipped[index] = !self.flipped[index];
+            }
+            SortEvent::Layer(layer) => self.layer = Some(layer),
+            SortEvent::Selected(idx) => self.selected = Some(idx),
+            SortEvent::Target(value) => self.target = Some(value),
+            SortEvent::Probe(idx) => self.probe = Some(idx),
+            SortEvent::Found(idx) => {
+                // Nothing is left to search, only the value found stays marked
+                self.found = Some(idx);
+                self.focus = None;
+            }
+            SortEvent::Unstash(slot) => {
+                self.aux[slot] = None;
+                self.aux_slot = Some((slot, false));
+            }
+            SortEvent::Withdraw(bucket) => {
+                self.buckets[bucket] = self.buckets[bucket].saturating_sub(1);
+                self.bucket = Some((bucket, false));
+            }
+            SortEvent::Swap(i, j) => {
+                // The pivot, current and candidate highlights follow their values through swaps
+                let follow = |idx: usize| match idx {
+                    _ if idx == i => j,
+                    _ if idx == j => i,
+                    _ => idx,
                 };
-
-                buffer += &format!("{}", styled_symbol);
+                for pivot in self.pivots.iter_mut() {
+                    *pivot = follow(*pivot);
+                }
+                for highlight in [&mut self.current, &mut self.candidate] {
+                    *highlight = highlight.map(follow);
+                }
             }
-            buffer.push('\n');
+            _ => {}
         }
-        // Show the cursor again
-        buffer.push_str("\x1B[?25h");
+    }
+    // Adds a sorted range, merging it with any it overlaps or touches
+    fn add_sorted(&mut self, mut start: usize, mut end: usize) {
+        self.sorted.retain(|&(other_start, other_end)| {
+            let apart = other_end < start || other_start > end;
+            if !apart {
+                start = start.min(other_start);
+                end = end.max(other_end);
+            }
+            apart
+        });
+        self.sorted.push((start, end));
+    }
+    // Position of the run holding `idx` among the runs
+    fn run_at(&self, idx: usize) -> Option<usize> {
+        self.runs
+            .iter()
+            .position(|&(start, end)| idx >= start && idx < end)
+    }
+    fn is_sorted(&self, idx: usize) -> bool {
+        self.sorted
+            .iter()
+            .any(|&(start, end)| idx >= start && idx < end)
+    }
+}
 
-        // Print the entire buffer at once to the terminal
-        print!("{}", buffer);
-        self.send_swap_values(swap_frequencies_from, swap_frequencies_to, 25);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bar_scale_covers_the_full_i32_range() {
+        let scale = BarScale::new(i32::MIN, i32::MAX, 20, "█", " ");
+        assert_eq!(scale.rows, 20);
+        assert_eq!(scale.height(i32::MIN), 1);
+        assert_eq!(scale.height(i32::MAX), 20);
+        assert_eq!(scale.height(0), 10);
+        // A range of fewer values than rows gets one row per value
+        let scale = BarScale::new(-1, 1, 20, "█", " ");
+        assert_eq!((scale.rows, scale.height(-1), scale.height(1)), (3, 1, 3));
     }
 }
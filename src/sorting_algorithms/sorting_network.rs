@@ -0,0 +1,170 @@
+use crate::sorting_algorithms::{Complexity, SortInfo, SortRecorder, Sorter};
+use std::cmp::Ordering;
+
+/// Layers of comparators for `len` wires. A comparator `(i, j)` puts the smaller of the
+/// values on wires `i` and `j` on `i`, and the comparators of one layer touch different wires,
+/// so a parallel machine runs each layer in one step.
+pub type Layers = Vec<Vec<(usize, usize)>>;
+
+// Runs the comparators layer by layer, marking where each layer starts
+fn run_network(array: &mut SortRecorder, layers: &Layers) {
+    for (idx, layer) in layers.iter().enumerate() {
+        array.layer(idx);
+        for &(i, j) in layer {
+            if array.compare(i, j) == Ordering::Greater {
+                array.swap(i, j);
+            }
+        }
+    }
+    array.sorted(0, array.len());
+}
+
+// Builds a layer over `size` wires, a power of two, from the wire each wire is compared with.
+// Comparators reaching past `len` are left out, as if the missing values were larger than
+// every real one, which keeps the network correct for any length.
+fn layer(size: usize, len: usize, partner: impl Fn(usize) -> Option<usize>) -> Vec<(usize, usize)> {
+    (0..size)
+        .filter_map(|i| partner(i).filter(|&j| j > i && j < len).map(|j| (i, j)))
+        .collect()
+}
+
+/// Bitonic sort as a sorting network. Sorted blocks are merged in pairs by comparing mirrored
+/// positions, which turns two sorted halves into a bitonic sequence that halving comparators
+/// finish sorting.
+#[derive(Default)]
+pub struct BitonicSort;
+
+impl Sorter for BitonicSort {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "bitonicsort",
+            title: "Bitonic Sorting Network",
+            stable: false,
+            in_place: true,
+            complexity: Complexity {
+                best: "O(n log^2 n)",
+                average: "O(n log^2 n)",
+                worst: "O(n log^2 n)",
+                space: "O(1)",
+            },
+        }
+    }
+
+    fn sort(&mut self, array: &mut SortRecorder) {
+        run_network(array, &Self::layers(array.len()));
+    }
+}
+
+impl BitonicSort {
+    pub fn new() -> Self {
+        BitonicSort
+    }
+    /// Returns the network's layers for `len` wires.
+    pub fn layers(len: usize) -> Layers {
+        let size = len.next_power_of_two();
+        let mut layers = Vec::new();
+        let mut block = 2;
+        while block <= size {
+            layers.push(layer(size, len, |i| {
+                let start = i - i % block;
+                Some(start + block - 1 - (i - start))
+            }));
+            let mut gap = block / 4;
+            while gap >= 1 {
+                layers.push(layer(size, len, |i| Some(i + gap).filter(|_| i & gap == 0)));
+                gap /= 2;
+            }
+            block *= 2;
+        }
+        layers.retain(|layer| !layer.is_empty());
+        layers
+    }
+}
+
+/// Batcher's odd-even merge sort as a sorting network. Two sorted halves are merged by merging
+/// their odd and even positions separately, then fixing up neighbors, which takes a few
+/// comparators less than bitonic sort.
+#[derive(Default)]
+pub struct OddEvenMergeSort;
+
+impl Sorter for OddEvenMergeSort {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "oddevenmergesort",
+            title: "Batcher Odd-Even Merge Sorting Network",
+            stable: false,
+            in_place: true,
+            complexity: Complexity {
+                best: "O(n log^2 n)",
+                average: "O(n log^2 n)",
+                worst: "O(n log^2 n)",
+                space: "O(1)",
+            },
+        }
+    }
+
+    fn sort(&mut self, array: &mut SortRecorder) {
+        run_network(array, &Self::layers(array.len()));
+    }
+}
+
+impl OddEvenMergeSort {
+    pub fn new() -> Self {
+        OddEvenMergeSort
+    }
+    /// Returns the network's layers for `len` wires.
+    pub fn layers(len: usize) -> Layers {
+        let size = len.next_power_of_two();
+        let mut layers = Vec::new();
+        let mut p = 1;
+        while p < size {
+            let mut k = p;
+            while k >= 1 {
+                layers.push(layer(size, len, |i| {
+                    // Wires `k` apart, in runs of `k` starting at `k % p`, within one block of `2p`
+                    let offset = i.checked_sub(k % p)?;
+                    let same_block = i / (2 * p) == (i + k) / (2 * p);
+                    Some(i + k).filter(|_| offset % (2 * k) < k && same_block)
+                }));
+                k /= 2;
+            }
+            p *= 2;
+        }
+        layers.retain(|layer| !layer.is_empty());
+        layers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // By the 0-1 principle, a network sorts every input once it sorts every input of zeros
+    // and ones
+    #[test]
+    fn networks_sort_every_zero_one_input() {
+        for len in 0..=12 {
+            for layers in [BitonicSort::layers(len), OddEvenMergeSort::layers(len)] {
+                for layer in layers.iter() {
+                    let mut wires: Vec<usize> = layer.iter().flat_map(|&(i, j)| [i, j]).collect();
+                    wires.sort_unstable();
+                    wires.dedup();
+                    assert_eq!(wires.len(), 2 * layer.len(), "{} wires share a layer", len);
+                }
+                for bits in 0u32..1 << len {
+                    let mut values: Vec<u32> = (0..len).map(|i| bits >> i & 1).collect();
+                    for &(i, j) in layers.iter().flatten() {
+                        if values[i] > values[j] {
+                            values.swap(i, j);
+                        }
+                    }
+                    assert!(
+                        values.windows(2).all(|pair| pair[0] <= pair[1]),
+                        "{:b}",
+                        bits
+                    );
+                }
+            }
+        }
+    }
+}
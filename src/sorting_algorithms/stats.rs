@@ -0,0 +1,61 @@
+use crate::sorting_algorithms::{SortEvent, SortTrace};
+use std::{fmt, time::Duration};
+
+/// Operation counts of a sorting run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SortStats {
+    pub comparisons: u64,
+    pub swaps: u64,
+    pub writes: u64,
+    pub probes: u64,       // Values a search compared with its target
+    pub aux_memory: usize, // Most values held outside the array at any one time
+    pub elapsed: Duration, // Time spent sorting, without any drawing or audio
+    aux_in_use: usize,
+}
+
+impl SortStats {
+    /// Empty counts for a run that took `elapsed` to sort.
+    pub fn new(elapsed: Duration) -> Self {
+        Self {
+            elapsed,
+            ..Self::default()
+        }
+    }
+
+    /// Counts every operation in `trace`.
+    pub fn from_trace(trace: &SortTrace) -> Self {
+        let mut stats = Self::new(trace.elapsed);
+        for event in trace.events.iter() {
+            stats.count(event);
+        }
+        stats
+    }
+
+    /// Adds the operation performed by `event`, if it is one.
+    pub fn count(&mut self, event: &SortEvent) {
+        match *event {
+            SortEvent::Compare(..) => self.comparisons += 1,
+            SortEvent::Swap(..) => self.swaps += 1,
+            SortEvent::Probe(_) => self.probes += 1,
+            SortEvent::Write { .. } | SortEvent::Push(_) | SortEvent::Stash { .. } => {
+                self.writes += 1
+            }
+            SortEvent::Alloc(len) => {
+                self.aux_in_use += len;
+                self.aux_memory = self.aux_memory.max(self.aux_in_use);
+            }
+            SortEvent::Free(len) => self.aux_in_use = self.aux_in_use.saturating_sub(len),
+            _ => {}
+        }
+    }
+}
+
+impl fmt::Display for SortStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "comparisons {}, swaps {}, writes {}, aux memory {}, time {:?}",
+            self.comparisons, self.swaps, self.writes, self.aux_memory, self.elapsed
+        )
+    }
+}
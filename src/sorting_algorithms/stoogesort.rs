@@ -0,0 +1,50 @@
+use crate::sorting_algorithms::{Complexity, SortInfo, SortRecorder, Sorter};
+
+/// Stooge sort orders the ends of a range, then sorts its first two thirds, its last two
+/// thirds and its first two thirds again. Correct, and famously slow at O(n^2.71).
+#[derive(Default)]
+pub struct StoogeSort;
+
+impl Sorter for StoogeSort {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "stoogesort",
+            title: "Stooge Sort Algorithm",
+            stable: false,
+            in_place: true,
+            complexity: Complexity {
+                best: "O(n^2.71)",
+                average: "O(n^2.71)",
+                worst: "O(n^2.71)",
+                space: "O(log n)",
+            },
+        }
+    }
+
+    fn sort(&mut self, array: &mut SortRecorder) {
+        let len = array.len();
+        if len > 1 {
+            Self::stooge(array, 0, len);
+        }
+        array.sorted(0, len);
+    }
+}
+
+impl StoogeSort {
+    pub fn new() -> Self {
+        StoogeSort
+    }
+    fn stooge(array: &mut SortRecorder, start: usize, end: usize) {
+        array.focus(start, end);
+        if array.less(end - 1, start) {
+            array.swap(start, end - 1);
+        }
+        let len = end - start;
+        if len > 2 {
+            let third = len / 3;
+            Self::stooge(array, start, end - third);
+            Self::stooge(array, start + third, end);
+            Self::stooge(array, start, end - third);
+        }
+    }
+}
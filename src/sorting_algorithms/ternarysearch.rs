@@ -0,0 +1,53 @@
+use crate::sorting_algorithms::{Complexity, SearchInfo, Searcher, SortRecorder};
+use std::cmp::Ordering;
+
+/// Ternary search. Two probes split the range into thirds and keep the one the target can be
+/// in. It needs more probes than binary search, as two probes only cut the range to a third
+/// where two binary search probes cut it to a quarter.
+#[derive(Default)]
+pub struct TernarySearch;
+
+impl Searcher for TernarySearch {
+    fn info(&self) -> SearchInfo {
+        SearchInfo {
+            name: "ternarysearch",
+            title: "Ternary Search Algorithm",
+            complexity: Complexity {
+                best: "O(1)",
+                average: "O(log n)",
+                worst: "O(log n)",
+                space: "O(1)",
+            },
+        }
+    }
+
+    fn search(&mut self, array: &mut SortRecorder, target: i32) -> Option<usize> {
+        let (mut low, mut high) = (0, array.len());
+        while low < high {
+            array.focus(low, high);
+            let third = (high - low) / 3;
+            let (left, right) = (low + third, high - 1 - third);
+            match array.probe(left, target) {
+                Ordering::Equal => return Some(left),
+                Ordering::Greater => {
+                    high = left;
+                    continue;
+                }
+                Ordering::Less => {}
+            }
+            match array.probe(right, target) {
+                Ordering::Equal => return Some(right),
+                Ordering::Less => low = right + 1,
+                Ordering::Greater => (low, high) = (left + 1, right),
+            }
+        }
+        array.focus(low, high);
+        None
+    }
+}
+
+impl TernarySearch {
+    pub fn new() -> Self {
+        TernarySearch
+    }
+}
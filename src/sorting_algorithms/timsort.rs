@@ -0,0 +1,263 @@
+use crate::sorting_algorithms::{BinaryInsertionSort, Complexity, SortInfo, SortRecorder, Sorter};
+use std::cmp::Ordering;
+
+// Inputs shorter than this are one binary insertion sorted run. CPython uses 64, lowered
+// here so runs and merges show up on inputs that fit the terminal.
+const MIN_MERGE: usize = 16;
+// Values one run must win in a row before a merge switches to galloping
+const MIN_GALLOP: usize = 7;
+
+/// Timsort, the stable sort of Python and Java. It finds the runs already in the input,
+/// extends short ones with binary insertion sort, and merges them off a stack that keeps
+/// merges balanced, galloping through a run when it keeps winning.
+#[derive(Default)]
+pub struct TimSort;
+
+impl Sorter for TimSort {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "timsort",
+            title: "Timsort Algorithm",
+            stable: true,
+            in_place: false,
+            complexity: Complexity {
+                best: "O(n)",
+                average: "O(n log n)",
+                worst: "O(n log n)",
+                space: "O(n)",
+            },
+        }
+    }
+
+    fn sort(&mut self, array: &mut SortRecorder) {
+        let len = array.len();
+        let min_run = Self::min_run(len);
+        // Pending runs as (start, length), in array order
+        let mut runs: Vec<(usize, usize)> = Vec::new();
+        let mut min_gallop = MIN_GALLOP;
+        let mut start = 0;
+        while start < len {
+            array.phase("finding runs");
+            let mut end = Self::find_run(array, start, len);
+            if end - start < min_run {
+                array.phase("extending run");
+                let extended = (start + min_run).min(len);
+                array.focus(start, extended);
+                BinaryInsertionSort::extend_sorted(array, start, end, extended);
+                end = extended;
+            }
+            array.run(start, end);
+            runs.push((start, end - start));
+            start = end;
+
+            // Merge until the run lengths shrink fast enough to keep merges balanced
+            while runs.len() > 1 {
+                let n = runs.len();
+                let length = |i: usize| runs[i].1;
+                let at = if (n >= 3 && length(n - 3) <= length(n - 2) + length(n - 1))
+                    || (n >= 4 && length(n - 4) <= length(n - 3) + length(n - 2))
+                {
+                    if length(n - 3) < length(n - 1) {
+                        n - 3
+                    } else {
+                        n - 2
+                    }
+                } else if length(n - 2) <= length(n - 1) {
+                    n - 2
+                } else {
+                    break;
+                };
+                Self::merge_at(array, &mut runs, at, &mut min_gallop);
+            }
+        }
+        while runs.len() > 1 {
+            let n = runs.len();
+            let at = if n >= 3 && runs[n - 3].1 < runs[n - 1].1 {
+                n - 3
+            } else {
+                n - 2
+            };
+            Self::merge_at(array, &mut runs, at, &mut min_gallop);
+        }
+    }
+}
+
+impl TimSort {
+    pub fn new() -> Self {
+        TimSort
+    }
+    // Shortest run worth merging, between MIN_MERGE / 2 and MIN_MERGE, chosen so `len` splits
+    // into a power of two runs or a little fewer
+    fn min_run(mut len: usize) -> usize {
+        let mut low_bits = 0;
+        while len >= MIN_MERGE {
+            low_bits |= len & 1;
+            len >>= 1;
+        }
+        len + low_bits
+    }
+    // Returns the end of the run starting at `start`, reversing it first if it is descending.
+    // Only strictly descending runs are reversed, so equal values keep their order.
+    fn find_run(array: &mut SortRecorder, start: usize, len: usize) -> usize {
+        let mut end = start + 1;
+        if end == len {
+            return end;
+        }
+        if array.less(end, end - 1) {
+            while end + 1 < len && array.less(end + 1, end) {
+                end += 1;
+            }
+            end += 1;
+            let (mut low, mut high) = (start, end - 1);
+            while low < high {
+                array.swap(low, high);
+                low += 1;
+                high -= 1;
+            }
+        } else {
+            while end + 1 < len && !array.less(end + 1, end) {
+                end += 1;
+            }
+            end += 1;
+        }
+        end
+    }
+    // Merges the runs at `at` and `at + 1` on the stack into one
+    fn merge_at(
+        array: &mut SortRecorder,
+        runs: &mut Vec<(usize, usize)>,
+        at: usize,
+        min_gallop: &mut usize,
+    ) {
+        let (start, left_len) = runs[at];
+        let (middle, right_len) = runs[at + 1];
+        runs[at] = (start, left_len + right_len);
+        runs.remove(at + 1);
+        Self::merge(array, start, middle, middle + right_len, min_gallop);
+    }
+    // Merges the sorted runs `start..middle` and `middle..end`, copying the left run out.
+    // Values are taken one at a time until one run wins MIN_GALLOP times in a row, then
+    // the merge gallops, finding how far that run keeps winning with an exponential search.
+    fn merge(
+        array: &mut SortRecorder,
+        start: usize,
+        middle: usize,
+        end: usize,
+        min_gallop: &mut usize,
+    ) {
+        array.split(start, middle, end);
+        let left_len = middle - start;
+        array.alloc(left_len);
+        for idx in start..middle {
+            array.stash(idx, idx);
+        }
+        // `i` counts the left values merged so far, they sit in the auxiliary slots from `start`
+        let (mut i, mut j, mut k) = (0, middle, start);
+
+        'merge: while i < left_len && j < end {
+            array.phase("merging");
+            let (mut left_wins, mut right_wins) = (0, 0);
+            while left_wins < *min_gallop && right_wins < *min_gallop {
+                if array.compare_values(j, array[j], k, array.aux(start + i)) == Ordering::Less {
+                    array.write(k, array[j]);
+                    j += 1;
+                    right_wins += 1;
+                    left_wins = 0;
+                } else {
+                    array.unstash(start + i, k);
+                    i += 1;
+                    left_wins += 1;
+                    right_wins = 0;
+                }
+                k += 1;
+                if i == left_len || j == end {
+                    break 'merge;
+                }
+            }
+
+            array.phase("galloping");
+            loop {
+                // Left values no greater than the next right value go first
+                let right = array[j];
+                let from_left = gallop(left_len - i, |offset| {
+                    array.compare_values(k + offset, array.aux(start + i + offset), j, right)
+                        != Ordering::Greater
+                });
+                for _ in 0..from_left {
+                    array.unstash(start + i, k);
+                    i += 1;
+                    k += 1;
+                }
+                if i == left_len {
+                    break 'merge;
+                }
+                array.write(k, array[j]);
+                j += 1;
+                k += 1;
+                if j == end {
+                    break 'merge;
+                }
+
+                // Then the right values strictly less than the next left value
+                let next = array.aux(start + i);
+                let from_right = gallop(end - j, |offset| {
+                    array.compare_values(j + offset, array[j + offset], k, next) == Ordering::Less
+                });
+                for _ in 0..from_right {
+                    array.write(k, array[j]);
+                    j += 1;
+                    k += 1;
+                }
+                if j == end {
+                    break 'merge;
+                }
+                array.unstash(start + i, k);
+                i += 1;
+                k += 1;
+                if i == left_len {
+                    break 'merge;
+                }
+
+                // Galloping pays off on long wins, and makes it easier to start again
+                if from_left < MIN_GALLOP && from_right < MIN_GALLOP {
+                    *min_gallop += 1;
+                    break;
+                }
+                *min_gallop = min_gallop.saturating_sub(1).max(1);
+            }
+        }
+        // Whatever is left of the left run goes at the end, the right run is already there
+        while i < left_len {
+            array.unstash(start + i, k);
+            i += 1;
+            k += 1;
+        }
+        array.free(left_len);
+    }
+}
+
+// Counts how many of the first `len` positions satisfy `goes_first`, which must hold for a
+// prefix of them. Probes 0, 2, 6, 14, ... until one fails, then binary searches the last gap.
+fn gallop(len: usize, mut goes_first: impl FnMut(usize) -> bool) -> usize {
+    let (mut low, mut step) = (0, 1);
+    let mut high = len;
+    while low + step - 1 < len {
+        let probe = low + step - 1;
+        if goes_first(probe) {
+            low = probe + 1;
+            step *= 2;
+        } else {
+            high = probe;
+            break;
+        }
+    }
+    while low < high {
+        let middle = (low + high) / 2;
+        if goes_first(middle) {
+            low = middle + 1;
+        } else {
+            high = middle;
+        }
+    }
+    low
+}
@@ -0,0 +1,313 @@
+use crate::sorting_algorithms::HeapShape;
+use std::{cmp::Ordering, ops::Index, time::Duration};
+
+/// A single step taken by a sorting algorithm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortEvent {
+    Compare(usize, usize),
+    Swap(usize, usize),
+    Write {
+        index: usize,
+        value: i32,
+    },
+    Push(i32),
+    Pop,
+    Pivot(usize),
+    Focus {
+        start: usize,
+        end: usize,
+    }, // Range the algorithm is working on, end exclusive
+    Split {
+        start: usize,
+        middle: usize,
+        end: usize,
+    }, // Two adjacent runs about to be merged
+    Current(usize), // Value the algorithm is currently placing, follows it through swaps
+    Candidate(usize), // Best value found so far in a scan, such as the running minimum
+    Sorted {
+        start: usize,
+        end: usize,
+    }, // Range known to be in order, end exclusive
+    Gap(usize),     // Distance between the values compared in the current pass
+    Buckets(usize), // Empty set of buckets or counts shown beside the array
+    Deposit {
+        index: usize,
+        bucket: usize,
+    }, // Value at `index` counted into `bucket`
+    Withdraw(usize), // Value taken back out of a bucket
+    Phase(&'static str), // Mode a hybrid sort switched to, shown beside the title
+    Run {
+        start: usize,
+        end: usize,
+    }, // Sorted run found in the input, end exclusive
+    Alloc(usize),   // Space for this many values taken outside the array
+    Free(usize),    // Space for this many values given back
+    Stash {
+        index: usize,
+        slot: usize,
+    }, // Value at `index` copied into `slot` of the auxiliary buffer
+    Unstash(usize), // Value taken back out of an auxiliary slot, right before it is written
+    Heap {
+        start: usize,
+        end: usize,
+        sorted_end: usize,
+        shape: HeapShape,
+    }, // `start..end` is a heap of `shape` and `end..sorted_end` its sorted tail
+    Flip(usize),    // Reverse bit of a weak heap node toggled, swapping its children
+    Selected(usize), // Value found by a selection algorithm, in its sorted place
+    Layer(usize),   // Start of a sorting network layer, whose comparators follow as compares
+    Target(i32),    // Value a search looks for, drawn as a line across the bars
+    Probe(usize),   // Value a search compared with its target
+    Found(usize),   // Index a search found its target at
+    GaveUp(usize),  // Run stopped at its cap after this many shuffles, leaving the values unsorted
+}
+
+/// The recorded run of a sorting algorithm, replayed by renderers and audio.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SortTrace {
+    pub initial: Vec<i32>,
+    pub events: Vec<SortEvent>,
+    pub output: Vec<i32>,
+    pub elapsed: Duration, // Time the algorithm took, not counting playback
+}
+
+impl SortTrace {
+    /// Applies the events to `initial` one at a time, calling `step` with the values after each.
+    pub fn replay(&self, mut step: impl FnMut(&[i32], &SortEvent)) {
+        let mut values = self.initial.clone();
+        for event in self.events.iter() {
+            event.apply(&mut values);
+            step(&values, event);
+        }
+    }
+}
+
+impl SortEvent {
+    /// Applies the event's effect on the array, if it has one.
+    pub fn apply(&self, values: &mut Vec<i32>) {
+        match *self {
+            SortEvent::Swap(i, j) => values.swap(i, j),
+            SortEvent::Write { index, value } => values[index] = value,
+            SortEvent::Push(value) => values.push(value),
+            SortEvent::Pop => {
+                values.pop();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Array handed to sorting algorithms. Every comparison and mutation goes through it
+/// so the run can be recorded as a `SortTrace` instead of drawn directly.
+#[derive(Debug, Clone, Default)]
+pub struct SortRecorder {
+    initial: Vec<i32>,
+    values: Vec<i32>,
+    events: Vec<SortEvent>,
+    key_shift: u32, // Low bits of each value ignored by comparisons, used to tag values
+    aux: Vec<i32>,  // Auxiliary buffer filled by `stash`
+}
+
+impl Index<usize> for SortRecorder {
+    type Output = i32;
+
+    fn index(&self, index: usize) -> &i32 {
+        &self.values[index]
+    }
+}
+
+impl SortRecorder {
+    pub fn new(values: Vec<i32>) -> Self {
+        Self {
+            initial: values.clone(),
+            values,
+            events: Vec::new(),
+            key_shift: 0,
+            aux: Vec::new(),
+        }
+    }
+    /// Creates a recorder that compares values by `value >> key_shift`, so the low bits can
+    /// carry a tag the algorithm does not see, such as the original position of each value.
+    pub fn with_key_shift(values: Vec<i32>, key_shift: u32) -> Self {
+        Self {
+            key_shift,
+            ..Self::new(values)
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+    pub fn values(&self) -> &[i32] {
+        &self.values
+    }
+    pub fn events(&self) -> &[SortEvent] {
+        &self.events
+    }
+    /// Returns the sort key of the value at `index`, without recording anything.
+    pub fn key(&self, index: usize) -> i32 {
+        self.key_of(self.values[index])
+    }
+    /// Returns the sort key of `value`, for values held outside the array.
+    pub fn key_of(&self, value: i32) -> i32 {
+        value >> self.key_shift
+    }
+
+    /// Compares the values at `i` and `j`.
+    pub fn compare(&mut self, i: usize, j: usize) -> Ordering {
+        self.events.push(SortEvent::Compare(i, j));
+        self.key(i).cmp(&self.key(j))
+    }
+    /// Returns true if the value at `i` is strictly less than the value at `j`.
+    pub fn less(&mut self, i: usize, j: usize) -> bool {
+        self.compare(i, j) == Ordering::Less
+    }
+    /// Compares two values held outside the array, highlighting the positions `i` and `j`
+    /// they were taken from.
+    pub fn compare_values(&mut self, i: usize, a: i32, j: usize, b: i32) -> Ordering {
+        self.events.push(SortEvent::Compare(i, j));
+        self.key_of(a).cmp(&self.key_of(b))
+    }
+
+    pub fn swap(&mut self, i: usize, j: usize) {
+        self.events.push(SortEvent::Swap(i, j));
+        self.values.swap(i, j);
+    }
+    pub fn write(&mut self, index: usize, value: i32) {
+        self.events.push(SortEvent::Write { index, value });
+        self.values[index] = value;
+    }
+    pub fn push(&mut self, value: i32) {
+        self.events.push(SortEvent::Push(value));
+        self.values.push(value);
+    }
+    pub fn pop(&mut self) -> Option<i32> {
+        let value = self.values.pop();
+        if value.is_some() {
+            self.events.push(SortEvent::Pop);
+        }
+        value
+    }
+
+    pub fn pivot(&mut self, index: usize) {
+        self.events.push(SortEvent::Pivot(index));
+    }
+    pub fn focus(&mut self, start: usize, end: usize) {
+        self.events.push(SortEvent::Focus { start, end });
+    }
+    pub fn split(&mut self, start: usize, middle: usize, end: usize) {
+        self.events.push(SortEvent::Split { start, middle, end });
+    }
+    pub fn current(&mut self, index: usize) {
+        self.events.push(SortEvent::Current(index));
+    }
+    pub fn candidate(&mut self, index: usize) {
+        self.events.push(SortEvent::Candidate(index));
+    }
+    pub fn sorted(&mut self, start: usize, end: usize) {
+        self.events.push(SortEvent::Sorted { start, end });
+    }
+    pub fn gap(&mut self, gap: usize) {
+        self.events.push(SortEvent::Gap(gap));
+    }
+    pub fn phase(&mut self, phase: &'static str) {
+        self.events.push(SortEvent::Phase(phase));
+    }
+    pub fn run(&mut self, start: usize, end: usize) {
+        self.events.push(SortEvent::Run { start, end });
+    }
+    /// Shows `count` empty buckets beside the array, or hides them when `count` is 0.
+    pub fn buckets(&mut self, count: usize) {
+        self.events.push(SortEvent::Buckets(count));
+    }
+    /// Notes that the value at `index` was counted into `bucket`.
+    pub fn deposit(&mut self, index: usize, bucket: usize) {
+        self.events.push(SortEvent::Deposit { index, bucket });
+    }
+    /// Notes that a value left `bucket`, usually right before it is written back.
+    pub fn withdraw(&mut self, bucket: usize) {
+        self.events.push(SortEvent::Withdraw(bucket));
+    }
+    /// Notes that the algorithm is holding `len` values in a buffer of its own.
+    pub fn alloc(&mut self, len: usize) {
+        self.events.push(SortEvent::Alloc(len));
+    }
+    /// Notes that a buffer of `len` values taken with `alloc` was released.
+    pub fn free(&mut self, len: usize) {
+        self.events.push(SortEvent::Free(len));
+    }
+    /// Draws `start..end` as a binary heap tree beside the bars, with `end..sorted_end` as its
+    /// detached sorted tail. Hides the tree when the range is empty.
+    pub fn heap(&mut self, start: usize, end: usize, sorted_end: usize) {
+        self.shaped_heap(HeapShape::BINARY, start, end, sorted_end);
+    }
+    /// Draws `start..end` as a heap of `shape`, like `heap` does for binary heaps.
+    pub fn shaped_heap(&mut self, shape: HeapShape, start: usize, end: usize, sorted_end: usize) {
+        self.events.push(SortEvent::Heap {
+            start,
+            end,
+            sorted_end,
+            shape,
+        });
+    }
+    /// Marks the value at `index` as the one a selection algorithm was looking for, now in
+    /// the place it would take in sorted order.
+    pub fn selected(&mut self, index: usize) {
+        self.events.push(SortEvent::Selected(index));
+    }
+    /// Ends a run that stopped at its cap of `shuffles` shuffles without sorting the values.
+    pub fn give_up(&mut self, shuffles: usize) {
+        self.events.push(SortEvent::GaveUp(shuffles));
+    }
+    /// Starts a search for `value`.
+    pub fn target(&mut self, value: i32) {
+        self.events.push(SortEvent::Target(value));
+    }
+    /// Compares the value at `index` with the value a search is looking for.
+    pub fn probe(&mut self, index: usize, target: i32) -> Ordering {
+        self.events.push(SortEvent::Probe(index));
+        self.key(index).cmp(&self.key_of(target))
+    }
+    /// Marks `index` as where a search found its target.
+    pub fn found(&mut self, index: usize) {
+        self.events.push(SortEvent::Found(index));
+    }
+    /// Toggles the reverse bit of weak heap node `index`, which swaps its two subtrees.
+    pub fn flip(&mut self, index: usize) {
+        self.events.push(SortEvent::Flip(index));
+    }
+    /// Starts layer `layer` of a sorting network. The compares up to the next layer are its
+    /// comparators, which are drawn as a network beside the bars.
+    pub fn layer(&mut self, layer: usize) {
+        self.events.push(SortEvent::Layer(layer));
+    }
+    /// Copies the value at `index` into `slot` of the auxiliary buffer, which is drawn as a
+    /// second row of bars under the array.
+    pub fn stash(&mut self, index: usize, slot: usize) {
+        if self.aux.len() <= slot {
+            self.aux.resize(slot + 1, 0);
+        }
+        self.aux[slot] = self.values[index];
+        self.events.push(SortEvent::Stash { index, slot });
+    }
+    /// Returns the value held in `slot` of the auxiliary buffer, without recording anything.
+    pub fn aux(&self, slot: usize) -> i32 {
+        self.aux[slot]
+    }
+    /// Writes the value held in `slot` of the auxiliary buffer back to `index`.
+    pub fn unstash(&mut self, slot: usize, index: usize) {
+        self.events.push(SortEvent::Unstash(slot));
+        self.write(index, self.aux[slot]);
+    }
+
+    pub fn into_trace(self) -> SortTrace {
+        SortTrace {
+            initial: self.initial,
+            events: self.events,
+            output: self.values,
+            elapsed: Duration::ZERO,
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use crate::sorting_algorithms::{SortEvent, SortRecorder, SortTrace, Sorter};
+use std::fmt;
+
+/// Result of checking a sorting run against its input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Verification {
+    pub sorted: bool,           // Output is in ascending order
+    pub permutation: bool,      // Output holds exactly the input values
+    pub stable: Option<bool>,   // Equal values kept their order, None when not checked
+    pub gave_up: Option<usize>, // Shuffles the sorter stopped after, when it hit its cap
+}
+
+impl Verification {
+    /// Checks `trace` and, when `sorter` claims to be stable, runs it again on tagged
+    /// duplicate keys to check that equal values keep their input order.
+    pub fn check(sorter: &mut dyn Sorter, trace: &SortTrace) -> Self {
+        let mut input = trace.initial.clone();
+        let mut output = trace.output.clone();
+        let sorted = output.windows(2).all(|pair| pair[0] <= pair[1]);
+        input.sort_unstable();
+        output.sort_unstable();
+        let gave_up = trace.events.iter().find_map(|event| match *event {
+            SortEvent::GaveUp(shuffles) => Some(shuffles),
+            _ => None,
+        });
+        let stable = match sorter.info().stable {
+            true => Self::check_stability(sorter, &trace.initial),
+            false => None,
+        };
+        Self {
+            sorted,
+            permutation: input == output,
+            stable,
+            gave_up,
+        }
+    }
+
+    /// Whether the run did what it was asked, which includes a sorter giving up at its cap
+    /// as long as it kept the input values.
+    pub fn passed(&self) -> bool {
+        let sorted = self.sorted || self.gave_up.is_some();
+        sorted && self.permutation && self.stable != Some(false)
+    }
+
+    // Tags every value with its input position in the low bits, hidden from comparisons,
+    // and checks that equal values come out with their tags in ascending order. Returns None
+    // when the values are too large to leave room for the tags.
+    fn check_stability(sorter: &mut dyn Sorter, values: &[i32]) -> Option<bool> {
+        let shift = usize::BITS - values.len().leading_zeros();
+        let tagged = values
+            .iter()
+            .enumerate()
+            .map(|(idx, &value)| value.checked_mul(1 << shift)?.checked_add(idx as i32))
+            .collect::<Option<Vec<i32>>>()?;
+        let mut array = SortRecorder::with_key_shift(tagged, shift);
+        sorter.sort(&mut array);
+
+        let mask = (1 << shift) - 1;
+        let stable = array.values().windows(2).all(|pair| {
+            let (left, right) = (pair[0], pair[1]);
+            left >> shift != right >> shift || left & mask < right & mask
+        });
+        Some(stable)
+    }
+}
+
+impl fmt::Display for Verification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut problems = Vec::new();
+        if !self.sorted && self.gave_up.is_none() {
+            problems.push("not sorted");
+        }
+        if !self.permutation {
+            problems.push("not a permutation of the input");
+        }
+        if self.stable == Some(false) {
+            problems.push("not stable");
+        }
+        if !problems.is_empty() {
+            return write!(f, "FAILED, {}", problems.join(", "));
+        }
+        match self.stable {
+            _ if self.gave_up.is_some() && !self.sorted => write!(
+                f,
+                "gave up after {} shuffles",
+                self.gave_up.unwrap_or_default()
+            ),
+            Some(true) => write!(f, "verified sorted and stable"),
+            _ => write!(f, "verified sorted"),
+        }
+    }
+}
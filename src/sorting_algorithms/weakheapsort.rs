@@ -0,0 +1,86 @@
+use crate::sorting_algorithms::{Complexity, HeapShape, SortInfo, SortRecorder, Sorter};
+
+/// Dutton's weak-heap sort. In a weak heap each value only has to be at least as large as the
+/// values in its right subtree, and a reverse bit per node swaps which subtree is the right
+/// one, so joining two heaps is one comparison and at most one swap. Sorting takes about
+/// n log n comparisons, fewer than any other heapsort. The reverse bits are its only extra
+/// memory, counted as the 32-bit words they pack into rather than as whole values.
+#[derive(Default)]
+pub struct WeakHeapSort;
+
+impl Sorter for WeakHeapSort {
+    fn info(&self) -> SortInfo {
+        SortInfo {
+            name: "weakheapsort",
+            title: "Weak-Heap Sort Algorithm",
+            stable: false,
+            in_place: false,
+            complexity: Complexity {
+                best: "O(n log n)",
+                average: "O(n log n)",
+                worst: "O(n log n)",
+                space: "O(n) bits",
+            },
+        }
+    }
+
+    fn sort(&mut self, array: &mut SortRecorder) {
+        let len = array.len();
+        if len <= 1 {
+            return;
+        }
+        // One reverse bit per node, shown in the tree by drawing its children swapped
+        let mut reversed = vec![false; len];
+        let words = len.div_ceil(32);
+        array.alloc(words);
+        array.shaped_heap(HeapShape::Weak, 0, len, len);
+        array.phase("building");
+        for idx in (1..len).rev() {
+            let ancestor = Self::distinguished_ancestor(&reversed, idx);
+            Self::join(array, &mut reversed, ancestor, idx);
+        }
+
+        array.phase("sifting");
+        for end in (2..len).rev() {
+            array.focus(0, end + 1);
+            array.swap(0, end);
+            array.shaped_heap(HeapShape::Weak, 0, end, len);
+            // Down the left spine of the root's subtree, then join each node with the root
+            // on the way back up
+            let mut idx = 1;
+            while 2 * idx + (reversed[idx] as usize) < end {
+                idx = 2 * idx + reversed[idx] as usize;
+            }
+            while idx > 0 {
+                Self::join(array, &mut reversed, 0, idx);
+                idx /= 2;
+            }
+        }
+        array.swap(0, 1);
+        array.free(words);
+        array.shaped_heap(HeapShape::Weak, 0, 0, 0);
+    }
+}
+
+impl WeakHeapSort {
+    pub fn new() -> Self {
+        WeakHeapSort
+    }
+    // Nearest ancestor of `idx` that has it in its right subtree
+    fn distinguished_ancestor(reversed: &[bool], mut idx: usize) -> usize {
+        // Climb while `idx` is a left child
+        while (idx & 1) == reversed[idx / 2] as usize {
+            idx /= 2;
+        }
+        idx / 2
+    }
+    // Makes the larger of the values at `ancestor` and `idx` the root of both, flipping
+    // `idx` so its old right subtree stays under the value it was ordered against
+    fn join(array: &mut SortRecorder, reversed: &mut [bool], ancestor: usize, idx: usize) {
+        if array.less(ancestor, idx) {
+            array.swap(ancestor, idx);
+            reversed[idx] = !reversed[idx];
+            array.flip(idx);
+        }
+    }
+}